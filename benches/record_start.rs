@@ -0,0 +1,58 @@
+//! Compare the throughput of the two record start rules.
+//! Run with `cargo bench --bench record_start`.
+
+use fire_fasta::{parse_fasta_with, ParseOptions, RecordStart};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const TOTAL_SIZE: usize = 64 << 20;
+const LINE_WIDTH: usize = 60;
+const ROUNDS: usize = 10;
+
+/// Generate a Multi FASTA file of roughly `TOTAL_SIZE` bytes with records of the given length.
+fn generate(record_len: usize) -> Vec<u8> {
+    let mut data = Vec::with_capacity(TOTAL_SIZE + record_len);
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut record = 0;
+    while data.len() < TOTAL_SIZE {
+        data.extend_from_slice(format!(">record{record} synthetic\n").as_bytes());
+        for i in 0..record_len {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            data.push(b"ACGT"[(state & 3) as usize]);
+            if (i + 1) % LINE_WIDTH == 0 || i + 1 == record_len {
+                data.push(b'\n');
+            }
+        }
+        record += 1;
+    }
+    data
+}
+
+/// Returns the fastest of `ROUNDS` parses of `data`.
+fn measure(data: &[u8], options: ParseOptions) -> Duration {
+    (0..ROUNDS)
+        .map(|_| {
+            let start = Instant::now();
+            let fasta = parse_fasta_with(black_box(data), options).expect("Failed to parse FASTA");
+            black_box(fasta.sequences.len());
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    for record_len in [100, 1_000, 100_000] {
+        let data = generate(record_len);
+        for (name, record_start) in [
+            ("memchr `>`  ", RecordStart::AnyGreaterThan),
+            ("memmem `\\n>`", RecordStart::LineStart),
+        ] {
+            let time = measure(&data, ParseOptions::new().record_start(record_start));
+            let throughput = data.len() as f64 / time.as_secs_f64() / f64::from(1 << 30);
+            println!("records of {record_len:>7} residues, {name}: {time:>10.2?} ({throughput:.2} GiB/s)");
+        }
+    }
+}
@@ -0,0 +1,85 @@
+#![no_main]
+
+use fire_fasta::{
+    parse_fasta_lenient, parse_fasta_validated, parse_fasta_with, parse_headers_only,
+    record_ranges, write_fasta, Fasta, FastaReader, FastaSequence, IndexedFasta, Newline,
+    ParseOptions, RecordStart, WriteOptions,
+};
+use libfuzzer_sys::fuzz_target;
+
+// Feed arbitrary bytes to every parsing entry point and exercise the parsed sequences.
+// The first byte selects the parse options, the rest is the FASTA input.
+fuzz_target!(|input: &[u8]| {
+    let Some((&selector, data)) = input.split_first() else {
+        return;
+    };
+
+    let mut options = ParseOptions::new();
+    if selector & 1 != 0 {
+        options = options.newline(Newline::Auto);
+    }
+    if selector & 2 != 0 {
+        options = options.max_records((selector >> 4) as usize);
+    }
+    if selector & 4 != 0 {
+        options = options.error_on_max_records(true);
+    }
+    if selector & 8 != 0 {
+        options = options.record_start(RecordStart::LineStart);
+    }
+
+    if let Ok(fasta) = parse_fasta_with(data, options) {
+        check_fasta(&fasta);
+    }
+
+    let (fasta, _) = parse_fasta_lenient(data);
+    check_fasta(&fasta);
+
+    let allowed = [selector & 8 == 0; 256];
+    if let Ok(fasta) = parse_fasta_validated(data, &allowed) {
+        check_fasta(&fasta);
+    }
+
+    let _ = parse_headers_only(data);
+    for range in record_ranges(data).flatten() {
+        assert!(range.0.end <= data.len() && range.1.end <= data.len());
+    }
+
+    if let Ok(index) = IndexedFasta::new(data) {
+        for i in 0..=index.len() {
+            if let Some(seq) = index.record(i) {
+                check_sequence(&seq);
+            }
+        }
+    }
+
+    let mut reader = FastaReader::new(data);
+    while let Ok(Some(seq)) = reader.next_record() {
+        check_sequence(&seq);
+    }
+});
+
+fn check_fasta(fasta: &Fasta) {
+    let _ = fasta.trailing_newline();
+    let _ = fasta.detect_line_width();
+    let mut out = Vec::new();
+    write_fasta(fasta, &mut out, &WriteOptions::new().line_width(3)).unwrap();
+
+    for seq in &fasta.sequences {
+        check_sequence(seq);
+    }
+}
+
+fn check_sequence(seq: &FastaSequence) {
+    let copied = seq.copy_sequential();
+    assert_eq!(seq.len(), copied.len());
+    assert_eq!(seq.iter().count(), copied.len());
+    assert!(copied.len() <= seq.size_hint());
+
+    let _ = seq.id();
+    let _ = seq.lines().count();
+    let _ = seq.newline_offsets();
+    let _ = seq.to_string_lossy();
+    let _ = seq.to_rna();
+    let _ = seq.codons(1).count();
+}
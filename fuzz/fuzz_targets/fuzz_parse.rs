@@ -0,0 +1,79 @@
+#![no_main]
+
+use fire_fasta::{parse_fasta, FastaSequence};
+use libfuzzer_sys::fuzz_target;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Feed arbitrary bytes to `parse_fasta`, and check that it neither panics nor allocates more than
+// linear memory in the input size.
+// Each record takes at least two bytes of input (a `>` and a newline), so the vector of records
+// holds at most `data.len() / 2 + 1` records, and at most twice as many because of its growth.
+fuzz_target!(|data: &[u8]| {
+    let baseline = LIVE.load(Ordering::Relaxed);
+    PEAK.store(baseline, Ordering::Relaxed);
+
+    let result = parse_fasta(data);
+
+    let allocated = PEAK.load(Ordering::Relaxed) - baseline;
+    let limit = std::mem::size_of::<FastaSequence>() * (data.len() + 4);
+    assert!(
+        allocated <= limit,
+        "allocated {allocated} bytes for {} bytes of input",
+        data.len()
+    );
+
+    if let Ok(fasta) = result {
+        let range = data.as_ptr_range();
+        for seq in &fasta.sequences {
+            assert!(range.contains(&seq.description.as_ptr()) || seq.description.is_empty());
+            assert!(seq.len() <= seq.size_hint());
+        }
+    }
+});
+
+/// The number of bytes currently allocated.
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+
+/// The highest number of bytes allocated at once since it was last reset.
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// An allocator that tracks the current and the peak heap usage.
+struct TrackingAllocator;
+
+impl TrackingAllocator {
+    fn grow(size: usize) {
+        let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            if new_size >= layout.size() {
+                Self::grow(new_size - layout.size());
+            } else {
+                LIVE.fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+            }
+        }
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: TrackingAllocator = TrackingAllocator;
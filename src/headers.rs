@@ -0,0 +1,106 @@
+//! Parsing of common header conventions.
+//! FASTA descriptions are free text, but most real-world files follow the conventions of the large
+//! sequence databases, which encode identifiers as `|`-separated fields in the ID.
+
+use crate::FastaSequence;
+
+/// The NCBI database tags that are followed by an accession.
+const NCBI_DATABASES: &[&[u8]] = &[
+    b"ref", b"gb", b"emb", b"dbj", b"pir", b"prf", b"pdb", b"tpg", b"tpe", b"tpd",
+];
+
+impl<'a> FastaSequence<'a> {
+    /// Returns the accession of the sequence, parsed from the [`id`] of the description.
+    /// The following conventions are recognized:
+    ///
+    /// - UniProt: `sp|P12345|NAME` and `tr|P12345|NAME` return the accession `P12345`,
+    /// - NCBI: `gi|12345|ref|NP_000.1|` returns the accession `NP_000.1` that follows the database
+    ///   tag, and `ref|NP_000.1|` (or any other NCBI database tag) returns `NP_000.1`,
+    /// - plain IDs without `|`: `P12345 desc` returns the ID `P12345`.
+    ///
+    /// Returns `None` if the ID is empty, or if it contains `|` but doesn't match a known
+    /// convention.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">sp|P69905|HBA_HUMAN Hemoglobin subunit alpha\nMVLSPADKTNVKAAWGKVGAHAGEYGAEALERMFLSFPTTKTYFPHF")?;
+    /// assert_eq!(fasta.sequences[0].accession(), Some(&b"P69905"[..]));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`id`]: FastaSequence::id
+    pub fn accession(&self) -> Option<&'a [u8]> {
+        let id = self.id();
+        if id.is_empty() {
+            return None;
+        }
+
+        let fields = id.split(|&c| c == b'|').collect::<Vec<_>>();
+        let accession = match fields.as_slice() {
+            [_] => id,
+            [b"sp" | b"tr", accession, ..] => accession,
+            [b"gi", _, database, accession, ..] if NCBI_DATABASES.contains(database) => accession,
+            [database, accession, ..] if NCBI_DATABASES.contains(database) => accession,
+            _ => return None,
+        };
+
+        Some(accession).filter(|accession| !accession.is_empty())
+    }
+
+    /// Returns the coordinate range encoded in the description as a `start-end` token,
+    /// like in `P32234 1-368`.
+    /// A range may also be attached to the end of a token with `/` or `:`,
+    /// like in the Pfam-style `P32234/1-368` or in `chr1:100-200`.
+    /// If the description contains multiple ranges, the last one is returned.
+    /// The bounds are returned as they appear, without checking that `start <= end`.
+    ///
+    /// Returns `None` if no token contains a range of two decimal numbers.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">P32234 1-368\nMSTIL")?;
+    /// assert_eq!(fasta.sequences[0].parsed_range(), Some((1, 368)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn parsed_range(&self) -> Option<(usize, usize)> {
+        self.description
+            .split(u8::is_ascii_whitespace)
+            .rev()
+            .find_map(|token| {
+                let range = match token.iter().rposition(|&c| c == b'/' || c == b':') {
+                    Some(separator) => &token[separator + 1..],
+                    None => token,
+                };
+                let dash = range.iter().position(|&c| c == b'-')?;
+                Some((
+                    parse_number(&range[..dash])?,
+                    parse_number(&range[dash + 1..])?,
+                ))
+            })
+    }
+}
+
+/// Parse a non-empty string of decimal digits, or return `None` if it contains any other character
+/// or overflows.
+fn parse_number(digits: &[u8]) -> Option<usize> {
+    if digits.is_empty() {
+        return None;
+    }
+
+    digits.iter().try_fold(0usize, |number, &c| {
+        if c.is_ascii_digit() {
+            number.checked_mul(10)?.checked_add(usize::from(c - b'0'))
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,72 @@
+use crate::parse_fasta_str;
+
+fn parsed_range(description: &str) -> Option<(usize, usize)> {
+    let data = format!(">{description}\nACGT");
+    let fasta = parse_fasta_str(&data).expect("Failed to parse FASTA");
+    fasta.sequences[0].parsed_range()
+}
+
+fn accession(description: &str) -> Option<String> {
+    let data = format!(">{description}\nACGT");
+    let fasta = parse_fasta_str(&data).expect("Failed to parse FASTA");
+    fasta.sequences[0]
+        .accession()
+        .map(|accession| String::from_utf8(accession.to_vec()).unwrap())
+}
+
+#[test]
+fn test_uniprot_accession() {
+    assert_eq!(
+        accession("sp|P12345|NAME_HUMAN desc").as_deref(),
+        Some("P12345")
+    );
+    assert_eq!(
+        accession("tr|A0A024R161|A0A024R161_HUMAN").as_deref(),
+        Some("A0A024R161")
+    );
+    assert_eq!(accession("sp||NAME"), None);
+}
+
+#[test]
+fn test_ncbi_accession() {
+    assert_eq!(
+        accession("gi|12345|ref|NP_000.1| desc").as_deref(),
+        Some("NP_000.1")
+    );
+    assert_eq!(
+        accession("gi|12345|gb|AAA00000.1|").as_deref(),
+        Some("AAA00000.1")
+    );
+    assert_eq!(accession("ref|NP_000.1|").as_deref(), Some("NP_000.1"));
+    assert_eq!(accession("gi|12345"), None);
+}
+
+#[test]
+fn test_plain_accession() {
+    assert_eq!(accession("P12345 desc").as_deref(), Some("P12345"));
+    assert_eq!(accession("NC_000913.3").as_deref(), Some("NC_000913.3"));
+    assert_eq!(accession(""), None);
+    assert_eq!(accession(" desc"), None);
+    assert_eq!(accession("foo|bar"), None);
+}
+
+#[test]
+fn test_parsed_range() {
+    assert_eq!(parsed_range("P32234 1-368"), Some((1, 368)));
+    assert_eq!(
+        parsed_range("P32234 12-40 some description"),
+        Some((12, 40))
+    );
+    assert_eq!(parsed_range("Q9XYZ1_HUMAN/12-345"), Some((12, 345)));
+    assert_eq!(parsed_range("chr1:100-200 region"), Some((100, 200)));
+    assert_eq!(parsed_range("a 1-2 b 3-4"), Some((3, 4)));
+    assert_eq!(parsed_range("seq 200-100"), Some((200, 100)));
+
+    assert_eq!(parsed_range("P32234"), None);
+    assert_eq!(parsed_range("P32234 gene-1"), None);
+    assert_eq!(parsed_range("P32234 1-"), None);
+    assert_eq!(parsed_range("P32234 -5"), None);
+    assert_eq!(parsed_range("P32234 1-2-3"), None);
+    assert_eq!(parsed_range("seq 1-99999999999999999999999"), None);
+    assert_eq!(parsed_range(""), None);
+}
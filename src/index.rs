@@ -0,0 +1,218 @@
+use crate::{record_ranges, FastaSequence, Newline, ParseError, ParseOptions, RecordRanges};
+use memchr::memchr;
+use std::ops::Range;
+
+/// An index of the record boundaries in a FASTA or Multi FASTA buffer,
+/// which provides random access to the records without re-parsing the buffer.
+/// It is the in-memory analog of random access using a `.fai` index.
+///
+/// The index stores only the byte span of each record (from its `>` to the end of its sequence)
+/// and the backing buffer.
+///
+/// ```rust
+/// # use fire_fasta::IndexedFasta;
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let data = b">Sample1\nACGTCA\n>Sample2\nACGTCC";
+/// let index = IndexedFasta::new(data)?;
+///
+/// assert_eq!(index.len(), 2);
+/// assert_eq!(index.record(1).unwrap().description, b"Sample2");
+/// assert!(index.record(2).is_none());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct IndexedFasta<'a> {
+    data: &'a [u8],
+    spans: Vec<Range<usize>>,
+}
+
+impl<'a> IndexedFasta<'a> {
+    /// Index the records of a FASTA or Multi FASTA file.
+    /// The records are identified with the same rules as in [`parse_fasta`].
+    ///
+    /// # Errors
+    /// Returns the same errors as [`parse_fasta`].
+    ///
+    /// [`parse_fasta`]: crate::parse_fasta
+    pub fn new(data: &'a [u8]) -> Result<Self, ParseError> {
+        let spans = record_ranges(data)
+            .spanned()
+            .map(|record| record.map(|(span, _)| span))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { data, spans })
+    }
+
+    /// Create an index from previously computed record spans over the same buffer,
+    /// for example spans obtained from [`spans`].
+    /// Each span must cover one record, from its `>` to the end of its sequence.
+    ///
+    /// [`spans`]: IndexedFasta::spans
+    pub fn from_spans(data: &'a [u8], spans: Vec<Range<usize>>) -> Self {
+        Self { data, spans }
+    }
+
+    /// Returns the record at index `i`, reconstructed from its span in the buffer.
+    /// Only the header line of the record is searched to separate the description from the
+    /// sequence.
+    ///
+    /// Returns `None` if the index is out of bounds, or if the span of the record doesn't describe
+    /// a valid record in the buffer.
+    pub fn record(&self, i: usize) -> Option<FastaSequence<'a>> {
+        let span = self.spans.get(i)?.clone();
+        let record = self.data.get(span.clone())?;
+        let record = record.strip_prefix(b">")?;
+        let header_end = memchr(b'\n', record)?;
+
+        Some(FastaSequence {
+            description: &record[..header_end],
+            sequence: &record[header_end + 1..],
+            newline: Newline::Lf,
+            strip_spaces: false,
+            span,
+        })
+    }
+
+    /// Returns the byte spans of all records in the buffer.
+    pub fn spans(&self) -> &[Range<usize>] {
+        &self.spans
+    }
+
+    /// Returns the number of records in the index.
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// Returns true if the index contains no records.
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+}
+
+/// A parsed FASTA or Multi FASTA file in a struct-of-arrays layout.
+/// Instead of a [`FastaSequence`] per record, it stores the byte ranges of all descriptions and
+/// all sequences in two separate vectors over the backing buffer,
+/// which improves the cache locality of bulk scans over only the descriptions or only the
+/// sequences, for example in files with millions of tiny records.
+/// Records are reconstructed as [`FastaSequence`]s on demand.
+///
+/// ```rust
+/// # use fire_fasta::FastaColumnar;
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let data = b">Sample1\nACGTCA\n>Sample2\nACGTCC";
+/// let fasta = FastaColumnar::new(data)?;
+///
+/// assert_eq!(fasta.descriptions(), &[1..8, 17..24]);
+/// assert_eq!(fasta.get(1).unwrap().copy_sequential().as_ref(), b"ACGTCC");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct FastaColumnar<'a> {
+    data: &'a [u8],
+    descriptions: Vec<Range<usize>>,
+    sequences: Vec<Range<usize>>,
+    /// The start of each record, from which the span of the record extends to the end of its
+    /// sequence.
+    record_starts: Vec<usize>,
+    newline: Newline,
+    strip_spaces: bool,
+}
+
+impl<'a> FastaColumnar<'a> {
+    /// Parse a FASTA or Multi FASTA file with the same rules as [`parse_fasta`].
+    ///
+    /// # Errors
+    /// Returns the same errors as [`parse_fasta`].
+    ///
+    /// [`parse_fasta`]: crate::parse_fasta
+    pub fn new(data: &'a [u8]) -> Result<Self, ParseError> {
+        Self::with_options(data, ParseOptions::default())
+    }
+
+    /// Parse a FASTA or Multi FASTA file with the given [`ParseOptions`],
+    /// like [`parse_fasta_with`].
+    ///
+    /// # Errors
+    /// Returns the same errors as [`parse_fasta_with`].
+    ///
+    /// [`parse_fasta_with`]: crate::parse_fasta_with
+    pub fn with_options(data: &'a [u8], options: ParseOptions) -> Result<Self, ParseError> {
+        let mut descriptions = Vec::new();
+        let mut sequences = Vec::new();
+        let mut record_starts = Vec::new();
+
+        let mut records = RecordRanges::with_options(data, options).spanned();
+        for record in records
+            .by_ref()
+            .take(options.max_records.unwrap_or(usize::MAX))
+        {
+            let (span, (mut header, sequence)) = record?;
+            if options.newline == Newline::Auto && data[header.clone()].ends_with(b"\r") {
+                header.end -= 1;
+            }
+            descriptions.push(header);
+            sequences.push(sequence);
+            record_starts.push(span.start);
+        }
+
+        if let Some(limit) = options.max_records {
+            if options.error_on_max_records && records.next().is_some() {
+                return Err(ParseError::TooManyRecords { limit });
+            }
+        }
+
+        Ok(Self {
+            data,
+            descriptions,
+            sequences,
+            record_starts,
+            newline: options.newline,
+            strip_spaces: options.strip_sequence_spaces,
+        })
+    }
+
+    /// Returns the record at index `i`, or `None` if the index is out of bounds.
+    pub fn get(&self, i: usize) -> Option<FastaSequence<'a>> {
+        let sequence = self.sequences.get(i)?.clone();
+        Some(FastaSequence {
+            description: &self.data[self.descriptions[i].clone()],
+            sequence: &self.data[sequence.clone()],
+            newline: self.newline,
+            strip_spaces: self.strip_spaces,
+            span: self.record_starts[i]..sequence.end,
+        })
+    }
+
+    /// Returns an iterator over all records, reconstructed in the order of the file.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = FastaSequence<'a>> + '_ {
+        (0..self.len()).map(|i| self.get(i).unwrap())
+    }
+
+    /// Returns the byte ranges of all descriptions in the buffer,
+    /// without the leading `>` and the trailing newline.
+    pub fn descriptions(&self) -> &[Range<usize>] {
+        &self.descriptions
+    }
+
+    /// Returns the byte ranges of all raw sequences (including newlines) in the buffer.
+    pub fn sequences(&self) -> &[Range<usize>] {
+        &self.sequences
+    }
+
+    /// Returns the number of records.
+    pub fn len(&self) -> usize {
+        self.sequences.len()
+    }
+
+    /// Returns true if the file contains no records.
+    pub fn is_empty(&self) -> bool {
+        self.sequences.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests;
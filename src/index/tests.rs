@@ -0,0 +1,79 @@
+use crate::{parse_fasta_with, FastaColumnar, IndexedFasta, Newline, ParseError, ParseOptions};
+
+#[test]
+fn test_indexed_records() {
+    let data = b">first desc\nACGT\nAC\n\n>second\nGG\n>\nT";
+    let index = IndexedFasta::new(data).expect("Failed to index FASTA");
+
+    assert_eq!(index.spans(), &[0..21, 21..32, 32..35]);
+
+    let second = index.record(1).unwrap();
+    assert_eq!(second.description, b"second");
+    assert_eq!(second.copy_sequential().as_ref(), b"GG");
+
+    let last = index.record(2).unwrap();
+    assert_eq!(last.description, b"");
+    assert_eq!(last.copy_sequential().as_ref(), b"T");
+
+    assert!(index.record(3).is_none());
+}
+
+#[test]
+fn test_indexed_from_spans() {
+    let data = b">a\nAC\n>b\nGT\n";
+    let spans = IndexedFasta::new(data).unwrap().spans().to_vec();
+
+    let index = IndexedFasta::from_spans(data, spans);
+    assert_eq!(index.record(0).unwrap().copy_sequential().as_ref(), b"AC");
+
+    let invalid = IndexedFasta::from_spans(data, vec![1..6, 6..20]);
+    assert!(invalid.record(0).is_none());
+    assert!(invalid.record(1).is_none());
+}
+
+#[test]
+fn test_indexed_errors() {
+    assert!(IndexedFasta::new(b"").unwrap().is_empty());
+    assert!(matches!(
+        IndexedFasta::new(b"ACGT"),
+        Err(ParseError::InvalidDescription { invalid: b'A' })
+    ));
+    assert!(matches!(
+        IndexedFasta::new(b">a\nAC\n>b"),
+        Err(ParseError::EmptySequence)
+    ));
+}
+
+#[test]
+fn test_columnar() {
+    let data = b">first desc\r\r\nACGT\r\nAC\n\n>second\nGG\n>\nT";
+    for options in [
+        ParseOptions::new(),
+        ParseOptions::new().newline(Newline::Auto),
+        ParseOptions::new().max_records(2),
+    ] {
+        let columnar = FastaColumnar::with_options(data, options).expect("Failed to parse FASTA");
+        let fasta = parse_fasta_with(data, options).expect("Failed to parse FASTA");
+
+        assert_eq!(columnar.len(), fasta.sequences.len());
+        for (record, expected) in columnar.iter().zip(&fasta.sequences) {
+            assert_eq!(record.description, expected.description);
+            assert_eq!(record.copy_sequential(), expected.copy_sequential());
+            assert_eq!(record.span, expected.span);
+        }
+        assert!(columnar.get(columnar.len()).is_none());
+    }
+
+    let columnar = FastaColumnar::new(data).unwrap();
+    assert_eq!(columnar.sequences()[1], 32..35);
+    assert!(matches!(
+        FastaColumnar::with_options(
+            data,
+            ParseOptions::new()
+                .max_records(1)
+                .error_on_max_records(true)
+        ),
+        Err(ParseError::TooManyRecords { limit: 1 })
+    ));
+    assert!(FastaColumnar::new(b"").unwrap().is_empty());
+}
@@ -18,8 +18,9 @@
 //! The parser expects input data that is compatible with ASCII.
 //! Multibyte UTF-8 codepoints are processed as separate ASCII characters.
 //!
-//! Windows-style newlines (`CRLF`) are not supported.
+//! Windows-style newlines (`CRLF`) are not supported by default.
 //! Instead, the parser treats the `LF` as a unix-style newline and preserve the `CR` as a valid sequence character.
+//! Files with `CRLF` or mixed newlines can be parsed with [`Newline::Auto`] using [`parse_fasta_with`].
 //! Old FASTA comments starting with `;` are also not supported, they are treated as part of the sequence.
 //!
 //! ### Usage and Lazy Parsing
@@ -51,9 +52,43 @@
 //! Parsing and copying use the [memchr](https://crates.io/crates/memchr) crate,
 //! and thus operations use SIMD instructions when available.
 
-use memchr::memchr;
+use crate::ranges::SpannedRecord;
+use memchr::{memchr, memchr2, memchr2_iter, memchr_iter, memmem, memrchr_iter};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
-use std::fmt::{Display, Formatter};
+use std::fmt::{Debug, Display, Formatter};
+use std::hash::BuildHasher;
+use std::ops::{Index, Range};
+use std::str::Utf8Error;
+
+pub mod headers;
+pub mod nucleotide;
+
+mod index;
+mod multi;
+mod options;
+mod owned;
+mod ranges;
+mod reader;
+mod sketch;
+mod state;
+mod stats;
+mod writer;
+
+pub use index::{FastaColumnar, IndexedFasta};
+pub use multi::MultiFileFasta;
+pub use options::{Newline, ParseOptions, RecordStart};
+pub use owned::{OwnedFasta, OwnedFastaSequence};
+pub use ranges::{record_ranges, RecordRanges};
+pub use reader::FastaReader;
+pub use sketch::{FnvBuildHasher, FnvHasher};
+pub use state::ParserState;
+pub use stats::FastaStats;
+pub use writer::{
+    rewrap_fasta, transform_fasta, write_fasta, write_fasta_records, write_fasta_sorted_by,
+    write_fasta_with_header_fn, write_fastq, FastaWriter, WriteOptions,
+};
 
 /// A Multi FASTA file containing zero, one, or more [`FastaSequences`].
 /// Access the sequences simply through its `sequences` field:
@@ -80,14 +115,665 @@ pub struct Fasta<'a> {
     pub sequences: Vec<FastaSequence<'a>>,
 }
 
+impl<'a> Fasta<'a> {
+    /// Returns an iterator over the sequences.
+    /// The iterator knows its exact length, so collecting from it allocates only once.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">Sample1\nACGT\n>Sample2\nAC")?;
+    ///
+    /// assert_eq!(fasta.iter().len(), 2);
+    /// for seq in &fasta {
+    ///     assert!(seq.description.starts_with(b"Sample"));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn iter(&self) -> std::slice::Iter<'_, FastaSequence<'a>> {
+        self.sequences.iter()
+    }
+
+    /// Returns an iterator over the sequences together with their 1-based record number,
+    /// following the conventional numbering of records in biological file formats.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">Sample1\nACGT\n>Sample2\nAC")?;
+    ///
+    /// let (number, seq) = fasta.enumerate_records().last().unwrap();
+    /// assert_eq!((number, seq.description), (2, &b"Sample2"[..]));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn enumerate_records(
+        &self,
+    ) -> impl ExactSizeIterator<Item = (usize, &FastaSequence<'a>)> + DoubleEndedIterator {
+        self.sequences
+            .iter()
+            .enumerate()
+            .map(|(i, seq)| (i + 1, seq))
+    }
+
+    /// Returns an iterator over the sequences together with the byte range of the full record in
+    /// the parsed input, from its `>` to the end of its sequence, just before the next `>`.
+    /// The range covers the description and the sequence exactly as they appear in the input,
+    /// including newlines, so the record can be copied verbatim.
+    /// For a sequence without a description (see [`ParseOptions::assume_leading_sequence`]),
+    /// the range covers only the sequence,
+    /// and for records separated by blank lines (see [`ParseOptions::blank_line_separator`]),
+    /// it starts at the description line.
+    ///
+    /// The span is recorded by the parser, so it refers to the input each sequence was parsed
+    /// from, even if sequences of other files are added to [`sequences`].
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let data = b">Sample1\nACGT\n>Sample2\nAC";
+    /// let fasta = parse_fasta(data)?;
+    ///
+    /// let (seq, span) = fasta.records_with_spans().last().unwrap();
+    /// assert_eq!(seq.description, b"Sample2");
+    /// assert_eq!(&data[span], b">Sample2\nAC");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`sequences`]: Fasta::sequences
+    pub fn records_with_spans(
+        &self,
+    ) -> impl Iterator<Item = (&FastaSequence<'a>, Range<usize>)> + '_ {
+        self.sequences.iter().map(|seq| (seq, seq.span.clone()))
+    }
+
+    /// Returns the total size of the records in the parsed input in bytes,
+    /// i.e. the sum of the lengths of their spans (see [`records_with_spans`]).
+    /// The spans are recorded by the parser, so the size is exact,
+    /// and only bytes outside of any record, such as skipped leading blank lines, are not counted.
+    /// Progress reports can weight records by their size instead of counting them,
+    /// which is more accurate if the record lengths vary widely.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">a\nACGT\n>b\nAC")?;
+    /// assert_eq!(fasta.estimated_bytes(), 13);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`records_with_spans`]: Fasta::records_with_spans
+    pub fn estimated_bytes(&self) -> usize {
+        self.sequences.iter().map(|seq| seq.span.len()).sum()
+    }
+
+    /// Sort the sequences lexicographically by their [`id`].
+    /// The sort is stable, so sequences with equal IDs keep their relative order.
+    ///
+    /// [`id`]: FastaSequence::id
+    pub fn sort_by_id(&mut self) {
+        self.sequences.sort_by_key(FastaSequence::id);
+    }
+
+    /// Sort the sequences by their length in descending order, so the longest sequence comes first.
+    /// The sort is stable, so sequences of equal length keep their relative order.
+    ///
+    /// The length of a sequence is its length without newlines (see [`len`]),
+    /// which is computed once per sequence before sorting.
+    ///
+    /// [`len`]: FastaSequence::len
+    pub fn sort_by_length(&mut self) {
+        self.sequences
+            .sort_by_cached_key(|seq| std::cmp::Reverse(seq.len()));
+    }
+
+    /// Binary search the sequences for a sequence with the given [`id`].
+    /// The sequences must be sorted by their ID (see [`sort_by_id`]),
+    /// otherwise the result is unspecified and meaningless, analogous to [`slice::binary_search`].
+    ///
+    /// # Returns
+    /// `Ok` with the index of a matching sequence, or `Err` with the index where a sequence with
+    /// the given ID could be inserted while keeping the order.
+    /// If multiple sequences share the ID, any one of them may be returned.
+    ///
+    /// [`id`]: FastaSequence::id
+    /// [`sort_by_id`]: Fasta::sort_by_id
+    pub fn binary_search_id(&self, id: &[u8]) -> Result<usize, usize> {
+        self.sequences.binary_search_by(|seq| seq.id().cmp(id))
+    }
+
+    /// Build an index from the [`id`] of each sequence to its index,
+    /// for repeated lookups by ID in unsorted files.
+    /// If multiple sequences share an ID, the index refers to the first of them.
+    /// The keys are borrowed from the input, so only the map itself is allocated.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">b desc\nACGT\n>a\nAC\n>b\nGG")?;
+    /// let index = fasta.index_by_id();
+    ///
+    /// assert_eq!(index.len(), 2);
+    /// assert_eq!(index[&b"a"[..]], 1);
+    /// assert_eq!(index[&b"b"[..]], 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`id`]: FastaSequence::id
+    pub fn index_by_id(&self) -> HashMap<&'a [u8], usize> {
+        let mut index = HashMap::with_capacity(self.sequences.len());
+        for (i, seq) in self.sequences.iter().enumerate() {
+            index.entry(seq.id()).or_insert(i);
+        }
+        index
+    }
+
+    /// Returns the first sequence with the given [`id`], or `None` if there is no such sequence.
+    /// This is a linear scan, so for repeated lookups, build an index with [`index_by_id`] instead.
+    ///
+    /// [`id`]: FastaSequence::id
+    /// [`index_by_id`]: Fasta::index_by_id
+    pub fn get_by_id(&self, id: &[u8]) -> Option<&FastaSequence<'a>> {
+        self.sequences.iter().find(|seq| seq.id() == id)
+    }
+
+    /// Build an index from the uppercase [`id`] of each sequence to its index,
+    /// for case-insensitive lookups in databases with inconsistent accession casing.
+    /// Only ASCII letters are uppercased, and lookups must uppercase the queried ID the same way
+    /// (see [`slice::to_ascii_uppercase`]), or use [`get_by_id_ci`].
+    /// If multiple sequences share an ID up to case, the index refers to the first of them.
+    /// In contrast to [`index_by_id`], the keys are copied to uppercase them.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">nc_000001 desc\nACGT\n>NC_000002\nAC")?;
+    /// let index = fasta.index_by_id_ci();
+    ///
+    /// assert_eq!(index[&b"NC_000001"[..]], 0);
+    /// assert_eq!(index[&b"nc_000002".to_ascii_uppercase()], 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`id`]: FastaSequence::id
+    /// [`get_by_id_ci`]: Fasta::get_by_id_ci
+    /// [`index_by_id`]: Fasta::index_by_id
+    pub fn index_by_id_ci(&self) -> HashMap<Vec<u8>, usize> {
+        let mut index = HashMap::with_capacity(self.sequences.len());
+        for (i, seq) in self.sequences.iter().enumerate() {
+            index.entry(seq.id().to_ascii_uppercase()).or_insert(i);
+        }
+        index
+    }
+
+    /// Returns the first sequence whose [`id`] matches the given ID, ignoring ASCII case,
+    /// or `None` if there is no such sequence.
+    /// This is a linear scan, so for repeated lookups, build an index with [`index_by_id_ci`]
+    /// instead.
+    ///
+    /// [`id`]: FastaSequence::id
+    /// [`index_by_id_ci`]: Fasta::index_by_id_ci
+    pub fn get_by_id_ci(&self, id: &[u8]) -> Option<&FastaSequence<'a>> {
+        self.sequences
+            .iter()
+            .find(|seq| seq.id().eq_ignore_ascii_case(id))
+    }
+
+    /// Returns one sequence per [`id`], keeping the sequence with the most residues among the
+    /// sequences that share an ID, for example when merging isoforms or re-runs of the same
+    /// sample.
+    /// The sequences are returned in the order their IDs first occur in the file.
+    /// If multiple sequences with the same ID have the same length, the first of them is kept.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">b v1\nAC\n>a\nGT\n>b v2\nACGT\n>b v3\nACG")?;
+    /// let descriptions = fasta
+    ///     .dedup_by_id_longest()
+    ///     .iter()
+    ///     .map(|seq| seq.description)
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(descriptions, vec![&b"b v2"[..], b"a"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`id`]: FastaSequence::id
+    pub fn dedup_by_id_longest(&self) -> Vec<&FastaSequence<'a>> {
+        let mut positions = HashMap::<&[u8], usize>::new();
+        let mut longest = Vec::<(&FastaSequence<'a>, usize)>::new();
+
+        for seq in &self.sequences {
+            let len = seq.len();
+            match positions.get(seq.id()) {
+                Some(&position) => {
+                    if len > longest[position].1 {
+                        longest[position] = (seq, len);
+                    }
+                }
+                None => {
+                    positions.insert(seq.id(), longest.len());
+                    longest.push((seq, len));
+                }
+            }
+        }
+
+        longest.into_iter().map(|(seq, _)| seq).collect()
+    }
+
+    /// Returns the longest prefix shared by the [`id`]s of all sequences,
+    /// for example to detect the accession namespace of a dataset (like `NC_` or `ENSG`).
+    /// Returns an empty prefix if the IDs share nothing or the file contains no sequences.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">NC_000001.11 chr1\nACGT\n>NC_000002.12\nAC")?;
+    /// assert_eq!(fasta.common_id_prefix(), b"NC_00000");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`id`]: FastaSequence::id
+    pub fn common_id_prefix(&self) -> Vec<u8> {
+        let mut ids = self.sequences.iter().map(FastaSequence::id);
+        let Some(mut prefix) = ids.next() else {
+            return Vec::new();
+        };
+
+        for id in ids {
+            let common = prefix.iter().zip(id).take_while(|(a, b)| a == b).count();
+            prefix = &prefix[..common];
+            if prefix.is_empty() {
+                break;
+            }
+        }
+        prefix.to_vec()
+    }
+
+    /// Returns an iterator over the sequences for which the predicate returns true.
+    /// The predicate receives the full [`FastaSequence`], so it can filter by any property,
+    /// such as the [`len`] of the sequence.
+    /// The iterator is lazy and borrows the sequences, so no data is copied.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">short\nACGT\n>long\nACGT\nACGT\n")?;
+    /// let mut long = fasta.filter_by_sequence(|seq| seq.len() > 4);
+    ///
+    /// assert_eq!(long.next().unwrap().description, b"long");
+    /// assert!(long.next().is_none());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`len`]: FastaSequence::len
+    pub fn filter_by_sequence<F: Fn(&FastaSequence) -> bool>(
+        &self,
+        pred: F,
+    ) -> impl Iterator<Item = &FastaSequence<'a>> {
+        self.sequences.iter().filter(move |seq| pred(seq))
+    }
+
+    /// Returns all sequences whose [`id`] is contained in `ids`, in the order of the file.
+    /// If multiple sequences share an ID, all of them are returned.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::collections::HashSet;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">P1 desc\nAC\n>P2\nGT\n>P3\nTT")?;
+    /// let ids = HashSet::from([&b"P3"[..], b"P1"]);
+    ///
+    /// let selected = fasta.select_ids(&ids);
+    /// assert_eq!(selected.len(), 2);
+    /// assert_eq!(selected[0].description, b"P1 desc");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`id`]: FastaSequence::id
+    pub fn select_ids<S: BuildHasher>(&self, ids: &HashSet<&[u8], S>) -> Vec<&FastaSequence<'a>> {
+        self.sequences
+            .iter()
+            .filter(|seq| ids.contains(seq.id()))
+            .collect()
+    }
+
+    /// Find all occurrences of `motif` in the residues of all sequences, skipping newlines,
+    /// so occurrences may span line breaks.
+    /// Overlapping occurrences are all reported.
+    /// The search uses [`memchr::memmem`].
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">a\nGAAT\nTC\n>b\nAATAAT")?;
+    /// assert_eq!(fasta.find_motif(b"AAT"), vec![(0, 1), (1, 0), (1, 3)]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Returns
+    /// The index of the record and the residue position of each occurrence,
+    /// ordered by record and position.
+    /// An empty motif has no occurrences.
+    pub fn find_motif(&self, motif: &[u8]) -> Vec<(usize, usize)> {
+        if motif.is_empty() {
+            return Vec::new();
+        }
+
+        let finder = memmem::Finder::new(motif);
+        let mut matches = Vec::new();
+        for (i, seq) in self.sequences.iter().enumerate() {
+            let residues = seq.residues();
+            let mut start = 0;
+            while let Some(pos) = finder.find(&residues[start..]) {
+                matches.push((i, start + pos));
+                start += pos + 1;
+            }
+        }
+        matches
+    }
+
+    /// Find all occurrences of `motif` like [`find_motif`], but compare ASCII letters
+    /// case-insensitively, so soft-masked (lowercase) regions don't hide matches.
+    /// Residues are case-folded during the comparison, without copying the sequences into an
+    /// uppercase buffer.
+    ///
+    /// [`find_motif`]: Fasta::find_motif
+    pub fn find_motif_ci(&self, motif: &[u8]) -> Vec<(usize, usize)> {
+        if motif.is_empty() {
+            return Vec::new();
+        }
+
+        let mut matches = Vec::new();
+        for (i, seq) in self.sequences.iter().enumerate() {
+            let residues = seq.residues();
+            matches.extend(
+                residues
+                    .windows(motif.len())
+                    .enumerate()
+                    .filter(|(_, window)| window.eq_ignore_ascii_case(motif))
+                    .map(|(pos, _)| (i, pos)),
+            );
+        }
+        matches
+    }
+
+    /// Detect the line width the sequences of the file are wrapped at.
+    /// The line width is consistent if in every sequence, all lines but the last one have the
+    /// same length, and no last line is longer than that.
+    /// Empty lines are ignored (see [`lines`]).
+    ///
+    /// # Returns
+    /// The common line width, or `None` if the line widths are inconsistent,
+    /// or if no sequence spans multiple lines, such that the wrapping cannot be determined.
+    ///
+    /// [`lines`]: FastaSequence::lines
+    pub fn detect_line_width(&self) -> Option<usize> {
+        let mut width = None;
+        let mut longest_last_line = 0;
+
+        for seq in &self.sequences {
+            let mut lines = seq.lines().peekable();
+            while let Some(line) = lines.next() {
+                if lines.peek().is_none() {
+                    longest_last_line = longest_last_line.max(line.len());
+                } else if *width.get_or_insert(line.len()) != line.len() {
+                    return None;
+                }
+            }
+        }
+
+        width.filter(|&width| longest_last_line <= width)
+    }
+
+    /// Concatenate the sequences of all records into one buffer, skipping newlines,
+    /// and separate consecutive sequences by the `separator` byte.
+    /// This is the input format of many index structures built over multiple sequences,
+    /// such as generalized suffix arrays or FM-indices.
+    ///
+    /// The caller is responsible for choosing a separator that doesn't occur in the sequences.
+    /// The buffer is allocated once.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">a\nAC\nGT\n>b\nTTA\n")?;
+    /// assert_eq!(fasta.join_residues(b'$'), b"ACGT$TTA");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn join_residues(&self, separator: u8) -> Vec<u8> {
+        let capacity = self
+            .sequences
+            .iter()
+            .map(FastaSequence::size_hint)
+            .sum::<usize>()
+            + self.sequences.len().saturating_sub(1);
+        let mut buffer = Vec::with_capacity(capacity);
+
+        for (i, seq) in self.sequences.iter().enumerate() {
+            if i > 0 {
+                buffer.push(separator);
+            }
+            seq.segments()
+                .for_each(|segment| buffer.extend_from_slice(segment));
+        }
+
+        buffer
+    }
+
+    /// Returns whether the parsed input ended with a newline.
+    /// Since the last sequence of a file extends to the end of the input,
+    /// this is determined by the last byte of the last sequence.
+    /// Writers can use this to decide whether to emit a final newline for byte-exact
+    /// re-serialization.
+    ///
+    /// Returns false if the file contains no sequences.
+    pub fn trailing_newline(&self) -> bool {
+        self.sequences
+            .last()
+            .is_some_and(|seq| seq.sequence.last() == Some(&b'\n'))
+    }
+
+    /// Returns the indices of all records whose description contains ASCII control characters
+    /// (see [`description_is_clean`]).
+    ///
+    /// [`description_is_clean`]: FastaSequence::description_is_clean
+    pub fn dirty_descriptions(&self) -> Vec<usize> {
+        self.sequences
+            .iter()
+            .enumerate()
+            .filter(|(_, seq)| !seq.description_is_clean())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Returns the number of records with an empty sequence (see [`FastaSequence::is_empty`]),
+    /// for example header-only records of sequences that failed to download.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">a\nACGT\n>b\n>c\n\n>d\nAC")?;
+    ///
+    /// assert_eq!(fasta.empty_sequence_count(), 2);
+    /// assert_eq!(fasta.empty_sequence_indices(), vec![1, 2]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn empty_sequence_count(&self) -> usize {
+        self.sequences.iter().filter(|seq| seq.is_empty()).count()
+    }
+
+    /// Returns the indices of all records with an empty sequence
+    /// (see [`empty_sequence_count`]).
+    ///
+    /// [`empty_sequence_count`]: Fasta::empty_sequence_count
+    pub fn empty_sequence_indices(&self) -> Vec<usize> {
+        self.sequences
+            .iter()
+            .enumerate()
+            .filter(|(_, seq)| seq.is_empty())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Returns the `p`-th percentile of the sequence lengths (see [`len`]), for `p` in `[0, 1]`.
+    /// Between two sequence lengths, the percentile is linearly interpolated and rounded to the
+    /// nearest integer.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">a\nACGT\n>b\nAC\n>c\nACGTACGT")?;
+    /// assert_eq!(fasta.length_percentile(0.5), Some(4));
+    /// assert_eq!(fasta.length_percentile(0.75), Some(6));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Returns
+    /// The percentile, or `None` if the file contains no sequences, or if `p` is not in `[0, 1]`.
+    ///
+    /// [`len`]: FastaSequence::len
+    pub fn length_percentile(&self, p: f64) -> Option<usize> {
+        percentile(&self.sorted_lengths(), p)
+    }
+
+    /// Returns the first quartile, the median, and the third quartile of the sequence lengths,
+    /// computed like [`length_percentile`].
+    /// The lengths are only computed and sorted once.
+    ///
+    /// Returns `None` if the file contains no sequences.
+    ///
+    /// [`length_percentile`]: Fasta::length_percentile
+    pub fn length_quartiles(&self) -> Option<[usize; 3]> {
+        let lengths = self.sorted_lengths();
+        Some([
+            percentile(&lengths, 0.25)?,
+            percentile(&lengths, 0.5)?,
+            percentile(&lengths, 0.75)?,
+        ])
+    }
+
+    /// Returns the lengths of all sequences in ascending order.
+    fn sorted_lengths(&self) -> Vec<usize> {
+        let mut lengths = self
+            .sequences
+            .iter()
+            .map(FastaSequence::len)
+            .collect::<Vec<_>>();
+        lengths.sort_unstable();
+        lengths
+    }
+}
+
+/// Returns the linearly interpolated `p`-th percentile of sorted values, rounded to the nearest
+/// integer, or `None` if there are no values or `p` is not in `[0, 1]`.
+fn percentile(sorted: &[usize], p: f64) -> Option<usize> {
+    if sorted.is_empty() || !(0.0..=1.0).contains(&p) {
+        return None;
+    }
+
+    let rank = p * (sorted.len() - 1) as f64;
+    let lower = sorted[rank.floor() as usize] as f64;
+    let upper = sorted[rank.ceil() as usize] as f64;
+    Some((lower + (upper - lower) * rank.fract()).round() as usize)
+}
+
+/// Two files are equal if they contain the same number of records,
+/// and the records are pairwise equal (see [`FastaSequence`]),
+/// i.e. they have the same descriptions and the same residues.
+/// Only the logical content is compared, not the raw byte layout,
+/// so files that only differ in line wrapping or newline convention are equal.
+///
+/// ```rust
+/// # use fire_fasta::parse_fasta;
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let wrapped = parse_fasta(b">a\nACG\nTAC\n>b\nGG\n")?;
+/// let unwrapped = parse_fasta(b">a\nACGTAC\n>b\nGG")?;
+///
+/// assert_eq!(wrapped, unwrapped);
+/// assert_ne!(wrapped, parse_fasta(b">a\nACGTAC\n")?);
+/// # Ok(())
+/// # }
+/// ```
+impl<'b> PartialEq<Fasta<'b>> for Fasta<'_> {
+    fn eq(&self, other: &Fasta<'b>) -> bool {
+        self.sequences == other.sequences
+    }
+}
+
+impl Eq for Fasta<'_> {}
+
+impl<'a> Index<usize> for Fasta<'a> {
+    type Output = FastaSequence<'a>;
+
+    /// Returns the sequence at the given index.
+    ///
+    /// # Panics
+    /// Panics if the index is out of bounds, like indexing a [`Vec`].
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.sequences[index]
+    }
+}
+
+impl<'b, 'a> IntoIterator for &'b Fasta<'a> {
+    type Item = &'b FastaSequence<'a>;
+    type IntoIter = std::slice::Iter<'b, FastaSequence<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.sequences.iter()
+    }
+}
+
+impl<'a> AsRef<[FastaSequence<'a>]> for Fasta<'a> {
+    fn as_ref(&self) -> &[FastaSequence<'a>] {
+        &self.sequences
+    }
+}
+
 /// A FASTA sequence with a description from a FASTA file.
 /// The sequence is not processed in any way, meaning accessing it performs further parsing when necessary.
-#[derive(Clone, Debug)]
+///
+/// The [`Debug`] representation truncates long sequences to their first and last residues.
+#[derive(Clone)]
 pub struct FastaSequence<'a> {
     /// A byte slice containing the sequence description (without the leading '>' character,
     /// and without the trailing newline.
     pub description: &'a [u8],
     sequence: &'a [u8],
+    newline: Newline,
+    strip_spaces: bool,
+    /// The byte range of the full record in the input it was parsed from,
+    /// see [`Fasta::records_with_spans`].
+    span: Range<usize>,
 }
 
 /// FASTA parsing error thrown during the initial parsing step in [`parse_fasta`]
@@ -110,30 +796,331 @@ pub enum ParseError {
 
     /// A valid descriptor was parsed, but no sequence is following
     EmptySequence,
+
+    /// A sequence contains a character that is not part of the allowed alphabet.
+    /// This error is only returned by [`parse_fasta_validated`].
+    ///
+    /// [`parse_fasta_validated`]: parse_fasta_validated
+    InvalidResidue {
+        /// The one-byte code point of the invalid sequence character.
+        byte: u8,
+        /// The byte offset of the invalid character in the input.
+        offset: usize,
+    },
+
+    /// The input contains more records than allowed by [`ParseOptions::max_records`].
+    /// This error is only returned if [`ParseOptions::error_on_max_records`] is set.
+    TooManyRecords {
+        /// The maximum number of records that was exceeded.
+        limit: usize,
+    },
+
+    /// A description line is longer than allowed by [`ParseOptions::max_header_len`].
+    HeaderTooLong {
+        /// The byte offset of the `>` that starts the description in the input.
+        offset: usize,
+    },
+
+    /// The input of [`parse_single_fasta`] contains more than one record.
+    MultipleRecords {
+        /// The byte offset of the `>` that starts the second record in the input.
+        offset: usize,
+    },
+
+    /// The input of [`parse_single_fasta`] contains no record.
+    NoRecords,
+
+    /// The input of [`parse_fasta_bounded`] is larger than allowed.
+    InputTooLarge {
+        /// The length of the input in bytes.
+        len: usize,
+        /// The maximum allowed length in bytes.
+        limit: usize,
+    },
+
+    /// A description is not valid UTF-8.
+    /// This error is only returned by [`parse_fasta_utf8_checked`].
+    InvalidUtf8Description {
+        /// The index of the record with the invalid description.
+        index: usize,
+    },
 }
 
 impl Display for ParseError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{self:?}")
+        match *self {
+            ParseError::InvalidDescription { invalid } => write!(
+                f,
+                "invalid description start character '{}' (byte {invalid:#04X}); \
+                 FASTA records must begin with '>'",
+                invalid.escape_ascii()
+            ),
+            ParseError::EmptySequence => write!(
+                f,
+                "the description at the end of the input has no following sequence"
+            ),
+            ParseError::InvalidResidue { byte, offset } => write!(
+                f,
+                "invalid sequence character '{}' (byte {byte:#04X}) at offset {offset}",
+                byte.escape_ascii()
+            ),
+            ParseError::TooManyRecords { limit } => {
+                write!(f, "the input contains more than {limit} records")
+            }
+            ParseError::HeaderTooLong { offset } => write!(
+                f,
+                "the description at offset {offset} is longer than the maximum length"
+            ),
+            ParseError::MultipleRecords { offset } => write!(
+                f,
+                "expected a single record, but another record starts at offset {offset}"
+            ),
+            ParseError::NoRecords => {
+                write!(f, "expected a single record, but the input contains none")
+            }
+            ParseError::InputTooLarge { len, limit } => write!(
+                f,
+                "the input of {len} bytes is larger than the maximum of {limit} bytes"
+            ),
+            ParseError::InvalidUtf8Description { index } => {
+                write!(f, "the description of record {index} is not valid UTF-8")
+            }
+        }
     }
 }
 
 impl Error for ParseError {}
 
+/// A suspicious, but recoverable, property of the input found by [`parse_fasta_lenient`].
+/// The index of the affected record is given for each warning.
+///
+/// [`parse_fasta_lenient`]: parse_fasta_lenient
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseWarning {
+    /// The input doesn't start with `>`.
+    /// The bytes before the first `>` are skipped.
+    UnexpectedLeadingBytes {
+        /// The number of skipped bytes.
+        count: usize,
+    },
+
+    /// The record has an empty description.
+    EmptyDescription {
+        /// The index of the record.
+        index: usize,
+    },
+
+    /// The record has no sequence characters apart from newlines.
+    EmptySequence {
+        /// The index of the record.
+        index: usize,
+    },
+
+    /// The description or the sequence of the record contains a carriage return (`CR`),
+    /// which hints at Windows-style newlines.
+    ContainsCarriageReturn {
+        /// The index of the record.
+        index: usize,
+    },
+}
+
 impl<'a> FastaSequence<'a> {
+    /// Returns the ID of the sequence, which is the first whitespace-delimited token of the
+    /// description.
+    /// If the description is empty or starts with whitespace, the ID is empty.
+    pub fn id(&self) -> &'a [u8] {
+        let end = self
+            .description
+            .iter()
+            .position(u8::is_ascii_whitespace)
+            .unwrap_or(self.description.len());
+        &self.description[..end]
+    }
+
+    /// Returns the number of sequence characters, excluding newlines.
+    /// This requires a pass over the sequence to count the newlines.
+    pub fn len(&self) -> usize {
+        match self.newline {
+            Newline::Lf if !self.strip_spaces => {
+                self.sequence.len() - count_newlines(self.sequence)
+            }
+            _ => self.segments().map(<[u8]>::len).sum(),
+        }
+    }
+
+    /// Returns true if the sequence contains no sequence characters.
+    /// This is a valid record, which occurs if the description is directly followed by the next
+    /// record, or if the sequence consists only of newlines (`>id\n\n\n>next`).
+    /// The search stops at the first sequence character.
+    pub fn is_empty(&self) -> bool {
+        self.segments().all(<[u8]>::is_empty)
+    }
+
+    /// Returns an iterator over the non-empty lines of the sequence, without their newlines.
+    /// Empty lines (e.g. blank lines between records) are skipped.
+    pub fn lines(&self) -> impl Iterator<Item = &'a [u8]> {
+        self.line_offsets()
+            .map(|(_, line)| line)
+            .filter(|line| !line.is_empty())
+    }
+
+    /// Returns the number of non-empty lines of the sequence (see [`lines`]).
+    ///
+    /// [`lines`]: FastaSequence::lines
+    pub fn line_count(&self) -> usize {
+        self.lines().count()
+    }
+
+    /// Returns an iterator over contiguous, newline-free spans of the sequence of at most `chunk`
+    /// residues each, which can be fed to vectorized routines directly.
+    /// The spans are borrowed from the input, and split at newlines and after every `chunk`
+    /// residues of a line, so a span never crosses a line break.
+    /// In contrast to [`lines`], long lines are thus split into multiple spans.
+    /// Empty lines yield no spans.
+    /// If spaces are skipped (see [`ParseOptions::strip_sequence_spaces`]),
+    /// the spans are split at spaces and tabs as well.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">seq\nACGTA\n\nCC\n")?;
+    /// let chunks = fasta.sequences[0].residue_chunks(2).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(chunks, vec![&b"AC"[..], b"GT", b"A", b"CC"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if `chunk` is `0`.
+    ///
+    /// [`lines`]: FastaSequence::lines
+    pub fn residue_chunks(&self, chunk: usize) -> impl Iterator<Item = &'a [u8]> {
+        assert!(chunk > 0, "chunk size must be non-zero");
+        self.segments()
+            .filter(|segment| !segment.is_empty())
+            .flat_map(move |segment| segment.chunks(chunk))
+    }
+
+    /// Returns the offsets of all newlines in the sequence, relative to the start of the sequence
+    /// (i.e. the first byte after the description line).
+    /// This allows mapping positions in the sequence back to lines and columns of the original
+    /// file layout.
+    ///
+    /// Since the last line of a sequence may or may not end in a newline,
+    /// a single-line sequence yields either no offset or exactly one.
+    pub fn newline_offsets(&self) -> Vec<usize> {
+        memchr_iter(b'\n', self.sequence).collect()
+    }
+
+    /// Map a residue position (excluding newlines) to the offset of the residue in the raw
+    /// sequence, relative to the start of the sequence like [`newline_offsets`].
+    /// Returns `None` if the position is not smaller than the sequence length.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">seq\nACG\nTA")?;
+    /// assert_eq!(fasta.sequences[0].logical_to_physical(3), Some(4));
+    /// assert_eq!(fasta.sequences[0].physical_to_logical(4), Some(3));
+    /// assert_eq!(fasta.sequences[0].physical_to_logical(3), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`newline_offsets`]: FastaSequence::newline_offsets
+    pub fn logical_to_physical(&self, mut logical: usize) -> Option<usize> {
+        for (start, segment) in self.segment_offsets() {
+            if logical < segment.len() {
+                return Some(start + logical);
+            }
+            logical -= segment.len();
+        }
+        None
+    }
+
+    /// Map an offset in the raw sequence to the position of the residue excluding newlines,
+    /// i.e. the inverse of [`logical_to_physical`].
+    /// Returns `None` if the offset is out of bounds, or if it points to a newline,
+    /// which has no residue position.
+    ///
+    /// [`logical_to_physical`]: FastaSequence::logical_to_physical
+    pub fn physical_to_logical(&self, physical: usize) -> Option<usize> {
+        let mut logical = 0;
+        for (start, segment) in self.segment_offsets() {
+            if physical < start {
+                return None;
+            }
+            if physical < start + segment.len() {
+                return Some(logical + physical - start);
+            }
+            logical += segment.len();
+        }
+        None
+    }
+
+    /// Returns true if the description contains no ASCII control characters,
+    /// such as a stray `CR` or tab, which break downstream tools that process descriptions as
+    /// tab-separated values.
+    pub fn description_is_clean(&self) -> bool {
+        !self.description.iter().any(u8::is_ascii_control)
+    }
+
     /// Returns an iterator over the FASTA sequence characters, excluding newlines.
-    /// Note that the parser expects unix-style line breaks, thus, CR-characters are preserved.
+    /// By default, the parser expects unix-style line breaks, thus, CR-characters are preserved,
+    /// unless the sequence was parsed with [`Newline::Auto`].
+    /// Spaces and tabs are preserved as well, unless the sequence was parsed with
+    /// [`ParseOptions::strip_sequence_spaces`].
     ///
     /// Newlines are filtered out on the fly, meaning that multiple calls to `iter` repeatedly
     /// search and skip them during iteration.
     #[inline]
-    pub fn iter(&self) -> impl Iterator<Item = &u8> {
-        self.sequence.iter().filter(|&x| *x != b'\n')
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &u8> {
+        let sequence = self.sequence;
+        if self.newline == Newline::Lf && !self.strip_spaces {
+            return Either::Left(sequence.iter().filter(|&&c| c != b'\n'));
+        }
+
+        // only look at neighboring bytes if a CR or spaces need to be skipped
+        let crlf = self.newline == Newline::Auto;
+        let strip_spaces = self.strip_spaces;
+        Either::Right(
+            sequence
+                .iter()
+                .enumerate()
+                .filter(move |&(i, &c)| {
+                    c != b'\n'
+                        && !(crlf && c == b'\r' && sequence.get(i + 1) == Some(&b'\n'))
+                        && !(strip_spaces && is_sequence_space(c))
+                })
+                .map(|(_, c)| c),
+        )
+    }
+
+    /// Returns an iterator over the FASTA sequence characters like [`iter`],
+    /// with ASCII letters converted to uppercase.
+    /// All other characters are yielded unchanged.
+    ///
+    /// [`iter`]: FastaSequence::iter
+    pub fn iter_uppercase(&self) -> impl DoubleEndedIterator<Item = u8> + '_ {
+        self.iter().map(u8::to_ascii_uppercase)
+    }
+
+    /// Returns an iterator over the FASTA sequence characters like [`iter`],
+    /// with ASCII letters converted to lowercase.
+    /// All other characters are yielded unchanged.
+    ///
+    /// [`iter`]: FastaSequence::iter
+    pub fn iter_lowercase(&self) -> impl DoubleEndedIterator<Item = u8> + '_ {
+        self.iter().map(u8::to_ascii_lowercase)
     }
 
     /// Copy the sequence into a consecutive memory region.
     /// This method allocates a buffer and copies the sequence into it, skipping newline symbols.
-    /// Note that any other symbols (including whitespace and line feeds) get preserved.
+    /// Note that any other symbols (including whitespace and line feeds) get preserved,
+    /// unless spaces are skipped (see [`ParseOptions::strip_sequence_spaces`]).
     /// The capacity of the return value may be larger than the actual sequence.
     /// It is guaranteed, however, that only one allocation is performed.
     #[must_use]
@@ -143,9 +1130,21 @@ impl<'a> FastaSequence<'a> {
         let mut pos = 0;
         loop {
             let pivot = memchr(b'\n', &self.sequence[pos..]).unwrap_or(self.sequence.len() - pos);
-            buffer[target..target + pivot].copy_from_slice(&self.sequence[pos..pos + pivot]);
+            let line = strip_line_end(
+                self.newline,
+                &self.sequence[pos..pos + pivot],
+                pos + pivot < self.sequence.len(),
+            );
+            if self.strip_spaces {
+                for &c in line.iter().filter(|&&c| !is_sequence_space(c)) {
+                    buffer[target] = c;
+                    target += 1;
+                }
+            } else {
+                buffer[target..target + line.len()].copy_from_slice(line);
+                target += line.len();
+            }
             pos += pivot + 1;
-            target += pivot;
 
             if pos >= self.sequence.len() {
                 break;
@@ -155,6 +1154,424 @@ impl<'a> FastaSequence<'a> {
         buffer.into_boxed_slice()
     }
 
+    /// Copy the residues for which `keep` returns true into a consecutive buffer,
+    /// skipping newlines, for example to keep only `ACGT` or to drop digits.
+    /// The buffer is allocated once with the [`size_hint`] of the sequence,
+    /// and truncated afterward.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">seq\n1 ACGT\n5 NNAC\n")?;
+    /// let acgt = fasta.sequences[0].retain_residues(|c| b"ACGT".contains(&c));
+    /// assert_eq!(acgt.as_ref(), b"ACGTAC");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`size_hint`]: FastaSequence::size_hint
+    #[must_use]
+    pub fn retain_residues<F: Fn(u8) -> bool>(&self, keep: F) -> Box<[u8]> {
+        let mut buffer = Vec::with_capacity(self.size_hint());
+        for segment in self.segments() {
+            buffer.extend(segment.iter().copied().filter(|&c| keep(c)));
+        }
+        buffer.into_boxed_slice()
+    }
+
+    /// Copy the residues into a consecutive buffer, skipping newlines and the gap characters `-`
+    /// and `.` of aligned sequences (see [`retain_residues`]).
+    ///
+    /// [`retain_residues`]: FastaSequence::retain_residues
+    #[must_use]
+    pub fn ungapped(&self) -> Box<[u8]> {
+        self.retain_residues(|c| c != b'-' && c != b'.')
+    }
+
+    /// Copy the residues into a consecutive buffer, skipping newlines and a single trailing `*`,
+    /// which marks the stop codon in translated protein sequences.
+    /// See [`trim_stop_with`] for other stop symbols.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">orf1\nMSTI\nLAA*\n>orf2\nMK*W*")?;
+    /// assert_eq!(fasta.sequences[0].trim_stop().as_ref(), b"MSTILAA");
+    /// assert!(!fasta.sequences[0].contains_internal_stop());
+    /// assert!(fasta.sequences[1].contains_internal_stop());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`trim_stop_with`]: FastaSequence::trim_stop_with
+    #[must_use]
+    pub fn trim_stop(&self) -> Box<[u8]> {
+        self.trim_stop_with(b'*')
+    }
+
+    /// Copy the residues into a consecutive buffer, skipping newlines and a single trailing
+    /// `stop` symbol.
+    #[must_use]
+    pub fn trim_stop_with(&self, stop: u8) -> Box<[u8]> {
+        let mut buffer = self.copy_sequential().into_vec();
+        if buffer.last() == Some(&stop) {
+            buffer.pop();
+        }
+        buffer.into_boxed_slice()
+    }
+
+    /// Returns true if the sequence contains a `*` that is not its last residue,
+    /// which flags pseudogenes or frameshifts in translated protein sequences.
+    /// See [`contains_internal_stop_with`] for other stop symbols.
+    ///
+    /// [`contains_internal_stop_with`]: FastaSequence::contains_internal_stop_with
+    pub fn contains_internal_stop(&self) -> bool {
+        self.contains_internal_stop_with(b'*')
+    }
+
+    /// Returns true if the sequence contains a `stop` symbol that is not its last residue.
+    /// The lines are searched with [`memchr`](https://crates.io/crates/memchr),
+    /// and the search ends at the first internal stop.
+    pub fn contains_internal_stop_with(&self, stop: u8) -> bool {
+        // whether the last residue seen so far is a stop
+        let mut trailing_stop = false;
+        for segment in self.segments().filter(|segment| !segment.is_empty()) {
+            if trailing_stop {
+                return true;
+            }
+            match memchr(stop, segment) {
+                Some(pos) if pos + 1 < segment.len() => return true,
+                Some(_) => trailing_stop = true,
+                None => {}
+            }
+        }
+        false
+    }
+
+    /// Returns an iterator over the FASTA sequence characters from back to front, excluding
+    /// newlines.
+    /// Like [`iter`], it doesn't allocate, but in contrast to `iter().rev()`, the newlines are
+    /// searched with the [memchr](https://crates.io/crates/memchr) crate,
+    /// so it is faster for long lines.
+    ///
+    /// [`iter`]: FastaSequence::iter
+    pub fn iter_rev(&self) -> impl Iterator<Item = &'a u8> {
+        let sequence = self.sequence;
+        let newline = self.newline;
+        let strip_spaces = self.strip_spaces;
+        let mut end = sequence.len();
+        memrchr_iter(b'\n', sequence)
+            .map(Some)
+            .chain(std::iter::once(None))
+            .map(move |pivot| {
+                let start = pivot.map_or(0, |pivot| pivot + 1);
+                let segment = &sequence[start..end];
+                let terminated = end < sequence.len();
+                end = pivot.unwrap_or(0);
+                strip_line_end(newline, segment, terminated)
+            })
+            .flat_map(|segment| segment.iter().rev())
+            .filter(move |&&c| !(strip_spaces && is_sequence_space(c)))
+    }
+
+    /// Returns the residues of a single-line sequence as a slice of the input, without copying.
+    /// The newline at the end of the line is excluded.
+    ///
+    /// This is an escape hatch for hot code paths, where the caller knows that the input isn't
+    /// wrapped.
+    /// The method is safe to call on any sequence, but if the sequence spans multiple lines,
+    /// the returned slice contains the internal newlines.
+    /// In debug builds, this is detected and the method panics instead.
+    /// Spaces and tabs are always contained in the slice,
+    /// even if they are skipped (see [`ParseOptions::strip_sequence_spaces`]).
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">seq\nACGTCA\n")?;
+    /// assert_eq!(fasta.sequences[0].as_residues_unchecked(), b"ACGTCA");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// Panics in debug builds, if the sequence contains a newline that is not at its end.
+    pub fn as_residues_unchecked(&self) -> &'a [u8] {
+        let (line, terminated) = match self.sequence.strip_suffix(b"\n") {
+            Some(line) => (line, true),
+            None => (self.sequence, false),
+        };
+        debug_assert!(
+            memchr(b'\n', line).is_none(),
+            "sequence contains internal newlines"
+        );
+        strip_line_end(self.newline, line, terminated)
+    }
+
+    /// Returns true if the residues of the sequence contain `needle` as a consecutive substring.
+    /// Newlines are skipped, so matches may span line breaks.
+    /// The search uses [`memchr::memmem`] and stops at the first match.
+    ///
+    /// Sequences on a single line are searched in place,
+    /// while multi-line sequences are copied into a buffer first (see [`residues`]).
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">seq\nACGTA\nCCGT")?;
+    /// assert!(fasta.sequences[0].contains(b"TACC"));
+    /// assert!(!fasta.sequences[0].contains(b"GTAA"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`residues`]: FastaSequence::residues
+    pub fn contains(&self, needle: &[u8]) -> bool {
+        memmem::find(&self.residues(), needle).is_some()
+    }
+
+    /// Returns true if the residues of the sequence start with `prefix`, skipping newlines,
+    /// for example to screen for a start codon or an adapter.
+    /// Only the first `prefix.len()` residues are read, and the comparison stops at the first
+    /// mismatch.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">seq\nAT\nGCCAAA\nAA")?;
+    /// assert!(fasta.sequences[0].residues_start_with(b"ATG"));
+    /// assert!(fasta.sequences[0].residues_end_with(b"AAAAA"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn residues_start_with(&self, prefix: &[u8]) -> bool {
+        let mut residues = self.iter();
+        prefix.iter().all(|c| residues.next() == Some(c))
+    }
+
+    /// Returns true if the residues of the sequence end with `suffix`, skipping newlines,
+    /// for example to screen for a poly-A tail.
+    /// The residues are read from the back with [`iter_rev`], and the comparison stops at the
+    /// first mismatch.
+    ///
+    /// [`iter_rev`]: FastaSequence::iter_rev
+    pub fn residues_end_with(&self, suffix: &[u8]) -> bool {
+        let mut residues = self.iter_rev();
+        suffix.iter().rev().all(|c| residues.next() == Some(c))
+    }
+
+    /// Returns the residues of the sequence without newlines.
+    /// If the sequence is on a single line, the residues are borrowed from the input
+    /// (see [`as_residues_unchecked`]), otherwise they are copied (see [`copy_sequential`]).
+    /// A single line containing skipped spaces or tabs is copied as well.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::borrow::Cow;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">a\nACGT\n>b\nAC\nGT\n")?;
+    /// assert!(matches!(fasta.sequences[0].residues(), Cow::Borrowed(b"ACGT")));
+    /// assert!(matches!(fasta.sequences[1].residues(), Cow::Owned(_)));
+    /// assert_eq!(fasta.sequences[1].residues().as_ref(), b"ACGT");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`as_residues_unchecked`]: FastaSequence::as_residues_unchecked
+    /// [`copy_sequential`]: FastaSequence::copy_sequential
+    pub fn residues(&self) -> Cow<'a, [u8]> {
+        match memchr(b'\n', self.sequence) {
+            Some(pivot) if pivot + 1 < self.sequence.len() => {
+                Cow::Owned(self.copy_sequential().into_vec())
+            }
+            _ if self.strip_spaces && memchr2(b' ', b'\t', self.sequence).is_some() => {
+                Cow::Owned(self.copy_sequential().into_vec())
+            }
+            _ => Cow::Borrowed(self.as_residues_unchecked()),
+        }
+    }
+
+    /// Copy the sequence into a consecutive buffer in reverse order, skipping newlines.
+    /// Only one allocation is performed.
+    #[must_use]
+    pub fn reverse(&self) -> Box<[u8]> {
+        let mut buffer = self.copy_sequential();
+        buffer.reverse();
+        buffer
+    }
+
+    /// Copy the sequence into two consecutive buffers, skipping newlines, split at the residue
+    /// position `index`, for example to separate a signal peptide from the mature protein.
+    /// The first buffer contains the residues before `index` and the second buffer the residues
+    /// from `index` on.
+    /// Both buffers are filled in a single pass over the sequence.
+    /// An `index` past the end of the sequence is clamped, so the second buffer is empty.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">seq\nMKV\nLAAG")?;
+    /// let (signal, mature) = fasta.sequences[0].split_at(4);
+    ///
+    /// assert_eq!(signal.as_ref(), b"MKVL");
+    /// assert_eq!(mature.as_ref(), b"AAG");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn split_at(&self, index: usize) -> (Box<[u8]>, Box<[u8]>) {
+        let len = self.len();
+        let index = index.min(len);
+        let mut head = Vec::with_capacity(index);
+        let mut tail = Vec::with_capacity(len - index);
+
+        for segment in self.segments() {
+            let (before, after) = segment.split_at((index - head.len()).min(segment.len()));
+            head.extend_from_slice(before);
+            tail.extend_from_slice(after);
+        }
+
+        (head.into_boxed_slice(), tail.into_boxed_slice())
+    }
+
+    /// Copy the residues in `[start, end)` into a consecutive buffer, skipping newlines.
+    /// The positions are 0-based residue positions, excluding newlines.
+    /// An `end` past the end of the sequence is clamped,
+    /// and if `start >= end`, the result is empty.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">seq\nAACG\nTTGA")?;
+    ///
+    /// assert_eq!(fasta.sequences[0].subsequence(2, 6).as_ref(), b"CGTT");
+    /// assert_eq!(fasta.sequences[0].subsequence(6, 100).as_ref(), b"GA");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn subsequence(&self, start: usize, end: usize) -> Box<[u8]> {
+        let mut buffer = Vec::with_capacity(end.saturating_sub(start).min(self.size_hint()));
+        let mut offset = 0;
+        for segment in self.segments() {
+            if offset >= end {
+                break;
+            }
+
+            let segment_start = offset;
+            offset += segment.len();
+            if offset > start {
+                let from = start.saturating_sub(segment_start);
+                let to = (end - segment_start).min(segment.len());
+                if from < to {
+                    buffer.extend_from_slice(&segment[from..to]);
+                }
+            }
+        }
+        buffer.into_boxed_slice()
+    }
+
+    /// Copy the residues around the residue at `pos` into a consecutive buffer, skipping newlines,
+    /// for example the context of a variant or a motif.
+    /// The window contains the `upstream` residues before `pos`, the residue at `pos`, and the
+    /// `downstream` residues after it, i.e. the residues in
+    /// `[pos - upstream, pos + downstream + 1)`.
+    /// The positions are 0-based residue positions, excluding newlines,
+    /// and the window is clamped to the bounds of the sequence.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">seq\nAACG\nTTGA")?;
+    ///
+    /// assert_eq!(fasta.sequences[0].flank(4, 2, 1).as_ref(), b"CGTT");
+    /// assert_eq!(fasta.sequences[0].flank(1, 3, 3).as_ref(), b"AACGT");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn flank(&self, pos: usize, upstream: usize, downstream: usize) -> Box<[u8]> {
+        let end = pos.saturating_add(downstream).saturating_add(1);
+        self.subsequence(pos.saturating_sub(upstream), end)
+    }
+
+    /// Returns an iterator over the newline-free segments of the sequence,
+    /// including empty segments between consecutive newlines.
+    /// If spaces are skipped, the lines are split at spaces and tabs as well.
+    fn segments(&self) -> impl Iterator<Item = &'a [u8]> {
+        self.segment_offsets().map(|(_, segment)| segment)
+    }
+
+    /// Returns an iterator over the newline-free segments of the sequence like [`segments`],
+    /// paired with their offsets in the sequence.
+    ///
+    /// [`segments`]: FastaSequence::segments
+    fn segment_offsets(&self) -> impl Iterator<Item = (usize, &'a [u8])> {
+        let strip_spaces = self.strip_spaces;
+        self.line_offsets().flat_map(move |(line_start, line)| {
+            // without skipped spaces, the line is a single segment
+            let separators: &[u8] = if strip_spaces { line } else { &[] };
+            let mut start = 0;
+            memchr2_iter(b' ', b'\t', separators)
+                .chain(std::iter::once(line.len()))
+                .map(move |end| {
+                    let segment_start = start;
+                    start = end + 1;
+                    (line_start + segment_start, &line[segment_start..end])
+                })
+        })
+    }
+
+    /// Returns an iterator over the lines of the sequence without their newlines,
+    /// paired with their offsets in the sequence.
+    /// Unlike [`segment_offsets`], the lines are not split at skipped spaces.
+    ///
+    /// [`segment_offsets`]: FastaSequence::segment_offsets
+    fn line_offsets(&self) -> impl Iterator<Item = (usize, &'a [u8])> {
+        let sequence = self.sequence;
+        let newline = self.newline;
+        let mut start = 0;
+        memchr_iter(b'\n', sequence)
+            .chain(std::iter::once(sequence.len()))
+            .map(move |end| {
+                let segment_start = start;
+                start = end + 1;
+                let segment = &sequence[segment_start..end];
+                (
+                    segment_start,
+                    strip_line_end(newline, segment, end < sequence.len()),
+                )
+            })
+    }
+
+    /// Copy the sequence into a [`String`], skipping newlines, and validate that it is UTF-8.
+    /// The buffer is allocated once and validated in place.
+    ///
+    /// # Errors
+    /// Returns a [`Utf8Error`] if the sequence is not valid UTF-8.
+    pub fn to_string(&self) -> Result<String, Utf8Error> {
+        String::from_utf8(self.copy_sequential().into_vec()).map_err(|e| e.utf8_error())
+    }
+
+    /// Copy the sequence into a [`String`], skipping newlines,
+    /// and replace invalid UTF-8 sequences with the replacement character `U+FFFD`.
+    /// If the sequence is valid UTF-8, no further allocation is performed.
+    pub fn to_string_lossy(&self) -> String {
+        match String::from_utf8(self.copy_sequential().into_vec()) {
+            Ok(string) => string,
+            Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+        }
+    }
+
     /// Returns the maximum size in bytes this sequence occupies.
     /// This size is a limit and could be smaller,
     /// for example if newlines are filtered out of the sequence (see [`copy_sequential`])
@@ -165,6 +1582,96 @@ impl<'a> FastaSequence<'a> {
     }
 }
 
+/// An iterator that is one of two iterator types with the same items,
+/// so a method can return a specialized iterator for a common case.
+enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+impl<T, L: Iterator<Item = T>, R: Iterator<Item = T>> Iterator for Either<L, R> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        match self {
+            Either::Left(iter) => iter.next(),
+            Either::Right(iter) => iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Either::Left(iter) => iter.size_hint(),
+            Either::Right(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl<T, L, R> DoubleEndedIterator for Either<L, R>
+where
+    L: DoubleEndedIterator<Item = T>,
+    R: DoubleEndedIterator<Item = T>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        match self {
+            Either::Left(iter) => iter.next_back(),
+            Either::Right(iter) => iter.next_back(),
+        }
+    }
+}
+
+/// The number of residues shown at each end of a truncated sequence in the [`Debug`]
+/// representation of [`FastaSequence`].
+const DEBUG_RESIDUES: usize = 16;
+
+impl Debug for FastaSequence<'_> {
+    /// Formats the full description, and the sequence without newlines,
+    /// truncated to the first and last 16 residues if it is longer,
+    /// together with the sequence length.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let len = self.len();
+        let sequence = if len <= 2 * DEBUG_RESIDUES {
+            String::from_utf8_lossy(&self.copy_sequential()).into_owned()
+        } else {
+            let head = self
+                .iter()
+                .take(DEBUG_RESIDUES)
+                .copied()
+                .collect::<Vec<_>>();
+            let mut tail = self
+                .iter_rev()
+                .take(DEBUG_RESIDUES)
+                .copied()
+                .collect::<Vec<_>>();
+            tail.reverse();
+            format!(
+                "{}...{}",
+                String::from_utf8_lossy(&head),
+                String::from_utf8_lossy(&tail)
+            )
+        };
+
+        f.debug_struct("FastaSequence")
+            .field("description", &String::from_utf8_lossy(self.description))
+            .field("sequence", &sequence)
+            .field("len", &len)
+            .finish()
+    }
+}
+
+/// Two sequences are equal if they have the same description and the same residues.
+/// Only the logical content is compared, not the raw byte layout,
+/// so sequences that only differ in line wrapping or newline convention are equal.
+impl<'b> PartialEq<FastaSequence<'b>> for FastaSequence<'_> {
+    fn eq(&self, other: &FastaSequence<'b>) -> bool {
+        self.description == other.description && self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for FastaSequence<'_> {}
+
 /// Parse a FASTA or Multi FASTA file.
 /// Sequence descriptions are expected to start with '>'.
 /// The deprecated comment character ';' is not parsed, neither for sequence descriptors nor for
@@ -184,10 +1691,49 @@ impl<'a> FastaSequence<'a> {
 ///
 /// [`InvalidDescription`]: ParseError::InvalidDescription
 /// [`EmptySequence`]: ParseError::EmptySequence
-pub fn parse_fasta_str(s: &str) -> Result<Fasta, ParseError> {
+pub fn parse_fasta_str(s: &str) -> Result<Fasta<'_>, ParseError> {
     parse_fasta(s.as_bytes())
 }
 
+/// Parse a FASTA or Multi FASTA file like [`parse_fasta`], and validate that every description is
+/// valid UTF-8, so the descriptions can be converted to `&str` without further checks.
+/// The sequences are not validated and stay bytes.
+///
+/// Note that [`parse_fasta_str`] doesn't validate anything itself,
+/// it only accepts input that is already known to be UTF-8.
+///
+/// ```rust
+/// # use fire_fasta::{parse_fasta_utf8_checked, ParseError};
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let fasta = parse_fasta_utf8_checked(">Sample1 α-globin\nACGT".as_bytes())?;
+/// assert_eq!(std::str::from_utf8(fasta[0].description)?, "Sample1 α-globin");
+///
+/// assert!(matches!(
+///     parse_fasta_utf8_checked(b">a\nACGT\n>b\xff\nAC"),
+///     Err(ParseError::InvalidUtf8Description { index: 1 })
+/// ));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns the same errors as [`parse_fasta`],
+/// and an [`InvalidUtf8Description`] error for the first description that is not valid UTF-8.
+///
+/// [`InvalidUtf8Description`]: ParseError::InvalidUtf8Description
+pub fn parse_fasta_utf8_checked(data: &[u8]) -> Result<Fasta<'_>, ParseError> {
+    let fasta = parse_fasta(data)?;
+    if let Some(index) = fasta
+        .sequences
+        .iter()
+        .position(|seq| std::str::from_utf8(seq.description).is_err())
+    {
+        return Err(ParseError::InvalidUtf8Description { index });
+    }
+    Ok(fasta)
+}
+
 /// Parse a FASTA or Multi FASTA file.
 /// Sequence descriptions are expected to start with '>'.
 /// The deprecated comment character ';' is not parsed, neither for sequence descriptors nor for
@@ -207,53 +1753,433 @@ pub fn parse_fasta_str(s: &str) -> Result<Fasta, ParseError> {
 ///
 /// [`InvalidDescription`]: ParseError::InvalidDescription
 /// [`EmptySequence`]: ParseError::EmptySequence
-pub fn parse_fasta(data: &[u8]) -> Result<Fasta, ParseError> {
+pub fn parse_fasta(data: &[u8]) -> Result<Fasta<'_>, ParseError> {
+    parse_fasta_with(data, ParseOptions::default())
+}
+
+/// Parse a FASTA or Multi FASTA file like [`parse_fasta`], but reject inputs larger than
+/// `max_bytes` before parsing.
+/// This makes the size limit explicit for services that parse untrusted uploads.
+///
+/// # Errors
+/// Returns an [`InputTooLarge`] error if `data` is longer than `max_bytes`,
+/// and otherwise the same errors as [`parse_fasta`].
+///
+/// [`InputTooLarge`]: ParseError::InputTooLarge
+pub fn parse_fasta_bounded(data: &[u8], max_bytes: usize) -> Result<Fasta<'_>, ParseError> {
+    if data.len() > max_bytes {
+        return Err(ParseError::InputTooLarge {
+            len: data.len(),
+            limit: max_bytes,
+        });
+    }
+
+    parse_fasta(data)
+}
+
+/// Parse a FASTA file that contains exactly one record, and return the record directly.
+/// The parser stops as soon as the end of the first record is found,
+/// so the content of any further records is not scanned.
+///
+/// ```rust
+/// # use fire_fasta::{parse_single_fasta, ParseError};
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let seq = parse_single_fasta(b">chr1\nACGT\nAC\n")?;
+/// assert_eq!(seq.description, b"chr1");
+///
+/// assert!(matches!(
+///     parse_single_fasta(b">a\nAC\n>b\nGT"),
+///     Err(ParseError::MultipleRecords { offset: 6 })
+/// ));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns the same errors as [`parse_fasta`] for the first record,
+/// a [`NoRecords`] error if the input is empty,
+/// and a [`MultipleRecords`] error if the first record is followed by another record.
+///
+/// [`NoRecords`]: ParseError::NoRecords
+/// [`MultipleRecords`]: ParseError::MultipleRecords
+pub fn parse_single_fasta(data: &[u8]) -> Result<FastaSequence<'_>, ParseError> {
+    let record = record_ranges(data)
+        .spanned()
+        .next()
+        .ok_or(ParseError::NoRecords)??;
+    if record.0.end < data.len() {
+        return Err(ParseError::MultipleRecords {
+            offset: record.0.end,
+        });
+    }
+
+    Ok(record_at(data, record, ParseOptions::default()))
+}
+
+/// Parse a FASTA or Multi FASTA file with the given [`ParseOptions`].
+/// With the default options, this function behaves exactly like [`parse_fasta`].
+///
+/// # Errors
+/// Returns the same errors as [`parse_fasta`].
+/// Additionally, if [`ParseOptions::error_on_max_records`] is set, the function returns a
+/// [`TooManyRecords`] error if the input contains more than [`ParseOptions::max_records`] records,
+/// and if [`ParseOptions::max_header_len`] is set, a [`HeaderTooLong`] error for the first
+/// description that exceeds it.
+///
+/// # Returns
+/// A [`Fasta`] instance containing the sequences from the Multi-Fasta file
+///
+/// [`TooManyRecords`]: ParseError::TooManyRecords
+/// [`HeaderTooLong`]: ParseError::HeaderTooLong
+pub fn parse_fasta_with(data: &[u8], options: ParseOptions) -> Result<Fasta<'_>, ParseError> {
     let mut sequences = Vec::new();
 
-    if data.is_empty() {
-        return Ok(Fasta { sequences });
+    let mut records = RecordRanges::with_options(data, options).spanned();
+
+    // the scan stops at the limit, so records after it are never scanned
+    let limit = options.max_records.unwrap_or(usize::MAX);
+    for record in records.by_ref().take(limit) {
+        sequences.push(record_at(data, record?, options));
+    }
+
+    if let Some(limit) = options.max_records {
+        // only look past the limit if exceeding it is an error
+        if options.error_on_max_records && records.next().is_some() {
+            return Err(ParseError::TooManyRecords { limit });
+        }
     }
 
-    let mut cursor = 0usize;
+    Ok(Fasta { sequences })
+}
 
-    loop {
-        if !expect(data, b'>', &mut cursor) {
-            return Err(ParseError::InvalidDescription {
-                invalid: data[cursor],
-            });
+/// Parse a FASTA or Multi FASTA file without failing, but collect warnings about suspicious input.
+/// This is a middle ground between the strict errors of [`parse_fasta`] and ignoring anomalies.
+/// Warnings are only created when an anomaly is detected, so well-formed input is parsed with
+/// negligible overhead.
+///
+/// Inputs that [`parse_fasta`] rejects are handled as follows:
+/// If the input doesn't start with `>`, all bytes until the first `>` are skipped
+/// and an [`UnexpectedLeadingBytes`] warning is emitted.
+/// If the input ends in a description without a sequence, the record is added with an empty sequence
+/// and an [`EmptySequence`] warning.
+///
+/// ```rust
+/// # use fire_fasta::{parse_fasta_lenient, ParseWarning};
+/// let (fasta, warnings) = parse_fasta_lenient(b">\nACGT\n>seq\r\nAC\r\n");
+///
+/// assert_eq!(fasta.sequences.len(), 2);
+/// assert_eq!(
+///     warnings,
+///     vec![
+///         ParseWarning::EmptyDescription { index: 0 },
+///         ParseWarning::ContainsCarriageReturn { index: 1 }
+///     ]
+/// );
+/// ```
+///
+/// # Returns
+/// A [`Fasta`] instance containing all sequences from the Multi-Fasta file,
+/// and the warnings in the order of the records they refer to.
+///
+/// [`UnexpectedLeadingBytes`]: ParseWarning::UnexpectedLeadingBytes
+/// [`EmptySequence`]: ParseWarning::EmptySequence
+pub fn parse_fasta_lenient(data: &[u8]) -> (Fasta<'_>, Vec<ParseWarning>) {
+    let mut sequences = Vec::new();
+    let mut warnings = Vec::new();
+
+    let offset = memchr(b'>', data).unwrap_or(data.len());
+    if offset > 0 {
+        warnings.push(ParseWarning::UnexpectedLeadingBytes { count: offset });
+    }
+
+    let remaining = &data[offset..];
+    let mut next_record = 0;
+    for record in record_ranges(remaining).spanned() {
+        let sequence = match record {
+            Ok((span, record)) => {
+                next_record = span.end;
+                let span = offset + span.start..offset + span.end;
+                record_at(remaining, (span, record), ParseOptions::default())
+            }
+            // every record of the scan starts with '>', so the only error is a description
+            // without a sequence at the end of the input
+            Err(_) => {
+                let header = &remaining[next_record + 1..];
+                let header_end = memchr(b'\n', header).unwrap_or(header.len());
+                FastaSequence {
+                    description: &header[..header_end],
+                    sequence: &[],
+                    newline: Newline::Lf,
+                    strip_spaces: false,
+                    span: offset + next_record..data.len(),
+                }
+            }
+        };
+
+        let index = sequences.len();
+        if sequence.description.is_empty() {
+            warnings.push(ParseWarning::EmptyDescription { index });
+        }
+        if sequence.is_empty() {
+            warnings.push(ParseWarning::EmptySequence { index });
         }
+        if memchr(b'\r', sequence.description).is_some()
+            || memchr(b'\r', sequence.sequence).is_some()
+        {
+            warnings.push(ParseWarning::ContainsCarriageReturn { index });
+        }
+
+        sequences.push(sequence);
+    }
+
+    (Fasta { sequences }, warnings)
+}
+
+/// Parse a FASTA or Multi FASTA file, and recover from malformed input instead of aborting.
+/// Each error is collected together with the byte offset of the malformed record,
+/// and parsing resynchronizes at the next description that starts a line (`\n>`).
+/// This way, all well-formed records of a file with a few corrupt records are returned.
+///
+/// ```rust
+/// # use fire_fasta::{parse_fasta_recovering, ParseError};
+/// let (fasta, errors) = parse_fasta_recovering(b"ACGT\n>seq1\nAC\n>seq2");
+///
+/// assert_eq!(fasta.sequences.len(), 1);
+/// assert_eq!(fasta.sequences[0].description, b"seq1");
+/// assert!(matches!(errors[0], (0, ParseError::InvalidDescription { invalid: b'A' })));
+/// assert!(matches!(errors[1], (14, ParseError::EmptySequence)));
+/// ```
+///
+/// # Returns
+/// A [`Fasta`] instance containing all well-formed records,
+/// and the errors of the malformed records in the order of their offsets.
+pub fn parse_fasta_recovering(data: &[u8]) -> (Fasta<'_>, Vec<(usize, ParseError)>) {
+    let mut sequences = Vec::new();
+    let mut errors = Vec::new();
 
-        let header_end = memchr(b'\n', &data[cursor..]).unwrap_or(data.len() - cursor);
-        let description = &data[cursor..cursor + header_end];
-        cursor += header_end + 1;
+    let mut offset = 0;
+    'resync: while offset < data.len() {
+        let remaining = &data[offset..];
 
-        if cursor >= data.len() {
-            return Err(ParseError::EmptySequence);
+        let mut next_record = 0;
+        for record in record_ranges(remaining).spanned() {
+            match record {
+                Ok((span, record)) => {
+                    next_record = span.end;
+                    let span = offset + span.start..offset + span.end;
+                    sequences.push(record_at(
+                        remaining,
+                        (span, record),
+                        ParseOptions::default(),
+                    ));
+                }
+                Err(err) => {
+                    errors.push((offset + next_record, err));
+                    match memmem::find(&remaining[next_record..], b"\n>") {
+                        Some(pivot) => {
+                            offset += next_record + pivot + 1;
+                            continue 'resync;
+                        }
+                        None => break 'resync,
+                    }
+                }
+            }
         }
+        break;
+    }
 
-        let sequence_end = memchr(b'>', &data[cursor..]).unwrap_or(data.len() - cursor);
-        // may contain trailing white space
-        let sequence = &data[cursor..cursor + sequence_end];
-        cursor += sequence_end;
+    (Fasta { sequences }, errors)
+}
 
-        sequences.push(FastaSequence {
-            description,
-            sequence,
-        });
+/// Parse a FASTA or Multi FASTA file and validate that all sequence characters are part of an
+/// allowed alphabet.
+/// The records are identified with the same scan as in [`parse_fasta`] (see [`record_ranges`]),
+/// and each record is validated as soon as it is found, so the input isn't traversed a second time
+/// after parsing.
+/// Newlines are always allowed, and the greater-than sign (`>`) always starts a new record.
+///
+/// The alphabet is given as a table that contains `true` at the index of every allowed byte:
+/// ```rust
+/// # use fire_fasta::{parse_fasta_validated, ParseError};
+/// let mut dna = [false; 256];
+/// for &base in b"ACGT" {
+///     dna[base as usize] = true;
+/// }
+///
+/// assert!(parse_fasta_validated(b">seq\nACGT\nGT", &dna).is_ok());
+/// assert!(matches!(
+///     parse_fasta_validated(b">seq\nACGT\nGU", &dna),
+///     Err(ParseError::InvalidResidue { byte: b'U', offset: 11 })
+/// ));
+/// ```
+///
+/// # Errors
+/// Returns the same errors as [`parse_fasta`], and additionally an [`InvalidResidue`] error for the
+/// first sequence character that is not allowed.
+///
+/// # Returns
+/// A [`Fasta`] instance containing all sequences from the Multi-Fasta file
+///
+/// [`InvalidResidue`]: ParseError::InvalidResidue
+pub fn parse_fasta_validated<'a>(
+    data: &'a [u8],
+    allowed: &[bool; 256],
+) -> Result<Fasta<'a>, ParseError> {
+    let mut sequences = Vec::new();
 
-        if cursor >= data.len() {
-            break;
+    for record in record_ranges(data).spanned() {
+        let record = record?;
+        let sequence_start = (record.1).1.start;
+        let sequence = record_at(data, record, ParseOptions::default());
+
+        // the record is validated right after it was identified, while it is still in the cache
+        let invalid = sequence
+            .segment_offsets()
+            .find_map(|(segment_start, segment)| {
+                segment
+                    .iter()
+                    .position(|&c| !allowed[c as usize])
+                    .map(|pos| (segment[pos], sequence_start + segment_start + pos))
+            });
+        if let Some((byte, offset)) = invalid {
+            return Err(ParseError::InvalidResidue { byte, offset });
         }
+
+        sequences.push(sequence);
     }
 
     Ok(Fasta { sequences })
 }
 
+/// Parse only the sequence descriptions of a FASTA or Multi FASTA file.
+/// Record boundaries are identified just like in [`parse_fasta`],
+/// but no [`FastaSequence`] values are constructed.
+/// This is useful for building a table of contents of a large file.
+///
+/// # Errors
+/// Returns the same errors as [`parse_fasta`]:
+/// An [`InvalidDescription`] error if the file doesn't start with `>`,
+/// and an [`EmptySequence`] error if the file ends in a description without a following sequence.
+///
+/// # Returns
+/// A vector containing the description (without the leading `>` and the trailing newline) of each
+/// record, together with the byte offset of the record's `>` character in `data`.
+///
+/// [`InvalidDescription`]: ParseError::InvalidDescription
+/// [`EmptySequence`]: ParseError::EmptySequence
+pub fn parse_headers_only(data: &[u8]) -> Result<Vec<(&[u8], usize)>, ParseError> {
+    record_ranges(data)
+        .map(|record| record.map(|(header, _)| (&data[header.start..header.end], header.start - 1)))
+        .collect()
+}
+
+/// Count the records of a FASTA or Multi FASTA file without allocating.
+/// The records are identified with the same scan as in [`parse_fasta`] (see [`record_ranges`]),
+/// so the count always matches the number of sequences [`parse_fasta`] would return.
+/// Note that this is not simply the number of `>` characters,
+/// since descriptions may contain `>` characters as well.
+///
+/// ```rust
+/// # use fire_fasta::count_records;
+/// assert_eq!(count_records(b">a\nACGT\n>b>c\nAC\n").unwrap(), 2);
+/// ```
+///
+/// # Errors
+/// Returns the same errors as [`parse_fasta`].
+pub fn count_records(data: &[u8]) -> Result<usize, ParseError> {
+    record_ranges(data).try_fold(0, |count, record| record.map(|_| count + 1))
+}
+
+/// Returns the fraction of positions at which two sequences have the same residue,
+/// skipping newlines.
+/// This is the column-wise identity of two records that are already aligned, such as the rows of a
+/// multiple sequence alignment: No alignment is performed, so gaps are compared like any other
+/// residue, and residues are compared case-sensitively.
+///
+/// Returns `None` if the sequences have different lengths or are both empty.
+///
+/// ```rust
+/// # use fire_fasta::{parse_fasta, percent_identity};
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let fasta = parse_fasta(b">a\nACGT\nACGT\n>b\nACG-ACCT\n>c\nAC")?;
+///
+/// assert_eq!(percent_identity(&fasta[0], &fasta[1]), Some(0.75));
+/// assert_eq!(percent_identity(&fasta[0], &fasta[2]), None);
+/// # Ok(())
+/// # }
+/// ```
+pub fn percent_identity(a: &FastaSequence, b: &FastaSequence) -> Option<f64> {
+    let len = a.len();
+    if len == 0 || len != b.len() {
+        return None;
+    }
+
+    let matches = a.iter().zip(b.iter()).filter(|(x, y)| x == y).count();
+    Some(matches as f64 / len as f64)
+}
+
+/// Create the record with the given description and sequence ranges in `data`.
+/// The sequence is interpreted according to the newline and space options.
+/// In [`Newline::Auto`] mode, a `CR` at the end of the description is stripped.
+/// The span of the record is stored as is, so it may refer to a larger input than `data`.
+fn record_at(
+    data: &[u8],
+    (span, (header, sequence)): SpannedRecord,
+    options: ParseOptions,
+) -> FastaSequence<'_> {
+    let newline = options.newline;
+    let mut description = &data[header];
+    if newline == Newline::Auto {
+        description = description.strip_suffix(b"\r").unwrap_or(description);
+    }
+
+    FastaSequence {
+        description,
+        sequence: &data[sequence],
+        newline,
+        strip_spaces: options.strip_sequence_spaces,
+        span,
+    }
+}
+
+/// Count the unix-style newlines (`LF`) in a byte slice.
+/// The search uses the [memchr](https://crates.io/crates/memchr) crate,
+/// and thus uses SIMD instructions when available.
+///
+/// ```rust
+/// # use fire_fasta::count_newlines;
+/// assert_eq!(count_newlines(b"ACGT\nACGT\n\nAC"), 3);
+/// ```
+#[inline]
+pub fn count_newlines(slice: &[u8]) -> usize {
+    memchr_iter(b'\n', slice).count()
+}
+
+/// Strip a trailing `CR` from a line, if the newline mode is [`Newline::Auto`] and the line is
+/// terminated by a `LF`.
+#[inline]
+fn strip_line_end(newline: Newline, line: &[u8], terminated: bool) -> &[u8] {
+    if newline == Newline::Auto && terminated {
+        line.strip_suffix(b"\r").unwrap_or(line)
+    } else {
+        line
+    }
+}
+
+/// Returns true if `c` is a space or tab, which is skipped in sequences parsed with
+/// [`ParseOptions::strip_sequence_spaces`].
+#[inline]
+fn is_sequence_space(c: u8) -> bool {
+    c == b' ' || c == b'\t'
+}
+
 /// Expect that the byte at [cursor] is equal to [expected]. If it is, advance the cursor by one.
-/// Returns false, if the byte is not equal to the expected byte.
+/// Returns false, if the byte is not equal to the expected byte, or if the cursor is out of bounds.
 #[inline]
 fn expect(data: &[u8], expected: u8, cursor: &mut usize) -> bool {
-    if data[*cursor] == expected {
+    if data.get(*cursor) == Some(&expected) {
         *cursor += 1;
         true
     } else {
@@ -0,0 +1,151 @@
+use crate::{parse_fasta, Fasta, FastaSequence, ParseError};
+use std::collections::HashMap;
+
+/// A collection of the records of several FASTA or Multi FASTA files,
+/// for example several reference files that are queried together.
+/// The records of all files are numbered consecutively in the order the files were added,
+/// and each record remembers the file it came from (see [`source_file`]).
+/// Records can be looked up by [`id`] across all files.
+///
+/// The collection borrows the buffers of all files, so they must outlive it,
+/// like the input of [`parse_fasta`].
+/// Memory-mapped files must thus stay mapped as long as the collection is used.
+///
+/// ```rust
+/// # use fire_fasta::MultiFileFasta;
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let mut fasta = MultiFileFasta::new();
+/// fasta.push(b">chr1\nACGT\n>chr2\nGG")?;
+/// fasta.push(b">plasmid1\nTTGA")?;
+///
+/// assert_eq!(fasta.len(), 3);
+/// assert_eq!(fasta.get_by_id(b"plasmid1").unwrap().description, b"plasmid1");
+/// assert_eq!(fasta.source_file(1), 0);
+/// assert_eq!(fasta.source_file(2), 1);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`source_file`]: MultiFileFasta::source_file
+/// [`id`]: FastaSequence::id
+#[derive(Clone, Debug, Default)]
+pub struct MultiFileFasta<'a> {
+    sequences: Vec<FastaSequence<'a>>,
+    /// The index of the first record of each file.
+    file_starts: Vec<usize>,
+    ids: HashMap<&'a [u8], usize>,
+}
+
+impl<'a> MultiFileFasta<'a> {
+    /// Create an empty collection.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a FASTA or Multi FASTA file with [`parse_fasta`], and add its records to the
+    /// collection.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`parse_fasta`].
+    /// If the file can't be parsed, the collection is left unchanged.
+    ///
+    /// # Returns
+    /// The index of the file in the collection.
+    pub fn push(&mut self, data: &'a [u8]) -> Result<usize, ParseError> {
+        let fasta = parse_fasta(data)?;
+        Ok(self.push_parsed(fasta))
+    }
+
+    /// Add the records of an already parsed file to the collection,
+    /// for example one parsed with [`parse_fasta_with`].
+    ///
+    /// # Returns
+    /// The index of the file in the collection.
+    ///
+    /// [`parse_fasta_with`]: crate::parse_fasta_with
+    pub fn push_parsed(&mut self, fasta: Fasta<'a>) -> usize {
+        let offset = self.sequences.len();
+        for (i, seq) in fasta.sequences.iter().enumerate() {
+            self.ids.entry(seq.id()).or_insert(offset + i);
+        }
+
+        self.file_starts.push(offset);
+        self.sequences.extend(fasta.sequences);
+        self.file_starts.len() - 1
+    }
+
+    /// Returns the record at index `i`, or `None` if the index is out of bounds.
+    pub fn get(&self, i: usize) -> Option<&FastaSequence<'a>> {
+        self.sequences.get(i)
+    }
+
+    /// Returns the first record with the given [`id`] across all files,
+    /// or `None` if there is no such record.
+    /// If multiple records share an ID, the record from the earliest added file wins,
+    /// and within a file, the first record wins.
+    /// The lookup uses an index that is built while adding files.
+    ///
+    /// [`id`]: FastaSequence::id
+    pub fn get_by_id(&self, id: &[u8]) -> Option<&FastaSequence<'a>> {
+        self.position_by_id(id).map(|i| &self.sequences[i])
+    }
+
+    /// Returns the index of the record that [`get_by_id`] returns,
+    /// which can be passed to [`source_file`].
+    ///
+    /// [`get_by_id`]: MultiFileFasta::get_by_id
+    /// [`source_file`]: MultiFileFasta::source_file
+    pub fn position_by_id(&self, id: &[u8]) -> Option<usize> {
+        self.ids.get(id).copied()
+    }
+
+    /// Returns the index of the file that the record at index `record` came from,
+    /// in the order the files were added.
+    ///
+    /// # Panics
+    /// Panics if `record` is out of bounds.
+    pub fn source_file(&self, record: usize) -> usize {
+        assert!(
+            record < self.sequences.len(),
+            "record index {record} out of bounds for {} records",
+            self.sequences.len()
+        );
+        // empty files share their start with the next file, so take the last file starting at or
+        // before the record
+        self.file_starts.partition_point(|&start| start <= record) - 1
+    }
+
+    /// Returns an iterator over all records together with the index of their source file.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (usize, &FastaSequence<'a>)> + '_ {
+        let mut file = 0;
+        self.sequences.iter().enumerate().map(move |(i, seq)| {
+            while self
+                .file_starts
+                .get(file + 1)
+                .is_some_and(|&start| start <= i)
+            {
+                file += 1;
+            }
+            (file, seq)
+        })
+    }
+
+    /// Returns the number of records in all files.
+    pub fn len(&self) -> usize {
+        self.sequences.len()
+    }
+
+    /// Returns true if the collection contains no records.
+    pub fn is_empty(&self) -> bool {
+        self.sequences.is_empty()
+    }
+
+    /// Returns the number of files added to the collection, including files without records.
+    pub fn file_count(&self) -> usize {
+        self.file_starts.len()
+    }
+}
+
+#[cfg(test)]
+mod tests;
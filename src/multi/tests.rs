@@ -0,0 +1,54 @@
+use crate::{parse_fasta_with, MultiFileFasta, Newline, ParseError, ParseOptions};
+
+#[test]
+fn test_multi_file_fasta() {
+    let mut fasta = MultiFileFasta::new();
+    assert_eq!(fasta.push(b">a x\nAC\n>b\nGT\n>a\nTT").unwrap(), 0);
+    assert_eq!(fasta.push(b"").unwrap(), 1);
+    let crlf = parse_fasta_with(
+        b">c\r\nAA\r\n>b\r\nCC\r\n",
+        ParseOptions::new().newline(Newline::Auto),
+    )
+    .expect("Failed to parse FASTA");
+    assert_eq!(fasta.push_parsed(crlf), 2);
+
+    assert_eq!(fasta.len(), 5);
+    assert_eq!(fasta.file_count(), 3);
+    assert_eq!(
+        (0..fasta.len())
+            .map(|i| fasta.source_file(i))
+            .collect::<Vec<_>>(),
+        vec![0, 0, 0, 2, 2]
+    );
+    assert_eq!(
+        fasta.iter().map(|(file, _)| file).collect::<Vec<_>>(),
+        vec![0, 0, 0, 2, 2]
+    );
+
+    // the first record with an ID wins
+    assert_eq!(fasta.get_by_id(b"a").unwrap().description, b"a x");
+    assert_eq!(fasta.position_by_id(b"b"), Some(1));
+    assert_eq!(fasta.position_by_id(b"c"), Some(3));
+    assert_eq!(
+        fasta.get_by_id(b"c").unwrap().copy_sequential().as_ref(),
+        b"AA"
+    );
+    assert!(fasta.get_by_id(b"d").is_none());
+    assert_eq!(fasta.get(4).unwrap().description, b"b");
+    assert!(fasta.get(5).is_none());
+
+    // a failing file doesn't change the collection
+    assert!(matches!(
+        fasta.push(b"ACGT"),
+        Err(ParseError::InvalidDescription { invalid: b'A' })
+    ));
+    assert_eq!((fasta.len(), fasta.file_count()), (5, 3));
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn test_source_file_out_of_bounds() {
+    let mut fasta = MultiFileFasta::new();
+    fasta.push(b">a\nAC").unwrap();
+    let _ = fasta.source_file(1);
+}
@@ -0,0 +1,699 @@
+//! Operations on nucleotide sequences.
+//! The core parser is alphabet-agnostic,
+//! while the methods in this module assume that sequences contain DNA or RNA bases.
+//! Characters that are not nucleotides are preserved unless documented otherwise.
+
+use crate::{Fasta, FastaSequence, OwnedFasta, OwnedFastaSequence};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+use std::ptr;
+
+/// A lookup table that maps each byte to its complementary base.
+/// Case is preserved, and bytes without a complement (like gaps) map to themselves.
+///
+/// ```rust
+/// # use fire_fasta::nucleotide::ComplementTable;
+/// let table = ComplementTable::dna();
+/// assert_eq!(table.complement(b'A'), b'T');
+/// assert_eq!(table.complement(b'r'), b'y');
+/// assert_eq!(table.complement(b'-'), b'-');
+/// ```
+#[derive(Clone, Debug)]
+pub struct ComplementTable([u8; 256]);
+
+impl ComplementTable {
+    /// The complement of the IUPAC nucleotide codes for DNA.
+    /// Uracil (`U`) is complemented to adenine (`A`).
+    pub fn dna() -> Self {
+        Self::from_pairs(b"ATCGRYKMBVDH", b'U', b'A')
+    }
+
+    /// The complement of the IUPAC nucleotide codes for RNA.
+    /// Thymine (`T`) is complemented to adenine (`A`).
+    pub fn rna() -> Self {
+        Self::from_pairs(b"AUCGRYKMBVDH", b'T', b'A')
+    }
+
+    /// Create a table from the given pairs of complementary uppercase bases,
+    /// and an additional one-way mapping of `from` to `to`.
+    fn from_pairs(pairs: &[u8], from: u8, to: u8) -> Self {
+        let mut table = [0; 256];
+        for (c, entry) in table.iter_mut().enumerate() {
+            *entry = c as u8;
+        }
+
+        let mut set = |a: u8, b: u8| {
+            table[a as usize] = b;
+            table[a.to_ascii_lowercase() as usize] = b.to_ascii_lowercase();
+        };
+        for pair in pairs.chunks_exact(2) {
+            set(pair[0], pair[1]);
+            set(pair[1], pair[0]);
+        }
+        set(from, to);
+
+        Self(table)
+    }
+
+    /// Returns the complement of a base.
+    #[inline]
+    pub fn complement(&self, base: u8) -> u8 {
+        self.0[base as usize]
+    }
+}
+
+/// Error of [`FastaSequence::pack_2bit`].
+#[derive(Clone, Debug)]
+pub enum PackError {
+    /// The sequence contains a character that is not one of the bases `A`, `C`, `G`, `T`.
+    InvalidBase {
+        /// The invalid character.
+        byte: u8,
+        /// The residue position of the invalid character in the sequence, excluding newlines.
+        position: usize,
+    },
+}
+
+impl Display for PackError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            PackError::InvalidBase { byte, position } => write!(
+                f,
+                "invalid base '{}' (byte {byte:#04X}) at position {position}; \
+                 only A, C, G, and T can be packed",
+                byte.escape_ascii()
+            ),
+        }
+    }
+}
+
+impl Error for PackError {}
+
+/// The base composition of a nucleotide sequence, counted case-insensitively
+/// (see [`FastaSequence::base_counts`]).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BaseCounts {
+    /// The number of adenine bases (`A`).
+    pub a: usize,
+    /// The number of cytosine bases (`C`).
+    pub c: usize,
+    /// The number of guanine bases (`G`).
+    pub g: usize,
+    /// The number of thymine bases (`T`).
+    pub t: usize,
+    /// The number of unknown bases (`N`).
+    pub n: usize,
+    /// The number of all other characters.
+    pub other: usize,
+}
+
+impl BaseCounts {
+    /// Returns the fraction of `G` and `C` bases among the `A`, `C`, `G`, and `T` bases,
+    /// ignoring unknown bases and other characters.
+    /// Returns `0.0` if there are no such bases.
+    pub fn gc_content(&self) -> f64 {
+        let total = self.a + self.c + self.g + self.t;
+        if total == 0 {
+            0.0
+        } else {
+            (self.g + self.c) as f64 / total as f64
+        }
+    }
+}
+
+/// How [`FastaSequence::masked_copy`] masks residues.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MaskMode {
+    /// Replace masked residues with `N`.
+    Hard,
+    /// Replace masked residues with their lowercase form.
+    Soft,
+}
+
+impl Default for ComplementTable {
+    fn default() -> Self {
+        Self::dna()
+    }
+}
+
+impl<'a> FastaSequence<'a> {
+    /// Copy the sequence into a consecutive buffer, skipping newlines, and transcribe it into RNA,
+    /// by replacing thymine (`T`) with uracil (`U`), preserving case.
+    /// All other characters are left untouched.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">seq\nACGT\nacgt")?;
+    /// assert_eq!(fasta.sequences[0].to_rna().as_ref(), b"ACGUacgu");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn to_rna(&self) -> Box<[u8]> {
+        let mut buffer = self.copy_sequential();
+        replace_base(&mut buffer, b'T', b'U');
+        buffer
+    }
+
+    /// Copy the sequence into a consecutive buffer, skipping newlines, and reverse-transcribe it
+    /// into DNA, by replacing uracil (`U`) with thymine (`T`), preserving case.
+    /// All other characters are left untouched.
+    #[must_use]
+    pub fn to_dna(&self) -> Box<[u8]> {
+        let mut buffer = self.copy_sequential();
+        replace_base(&mut buffer, b'U', b'T');
+        buffer
+    }
+
+    /// Returns an iterator over the codons of the sequence in the given reading frame.
+    /// The frame is the number of residues skipped at the start of the sequence,
+    /// so frames `0`, `1`, and `2` are the three forward reading frames.
+    /// Newlines are skipped, so codons may span line breaks.
+    /// A trailing partial codon is dropped.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">seq\nATGG\nCCTA")?;
+    /// let codons = fasta.sequences[0].codons(1).collect::<Vec<_>>();
+    /// assert_eq!(codons, vec![*b"TGG", *b"CCT"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn codons(&self, frame: usize) -> impl Iterator<Item = [u8; 3]> + '_ {
+        let mut residues = self.iter().copied().skip(frame);
+        std::iter::from_fn(move || Some([residues.next()?, residues.next()?, residues.next()?]))
+    }
+
+    /// Count the bases of the sequence case-insensitively in one pass, skipping newlines.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">seq\nACGTN\nggc-")?;
+    /// let counts = fasta.sequences[0].base_counts();
+    ///
+    /// assert_eq!((counts.g, counts.n, counts.other), (3, 1, 1));
+    /// assert_eq!(counts.gc_content(), 5.0 / 7.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn base_counts(&self) -> BaseCounts {
+        let mut counts = BaseCounts::default();
+        for segment in self.segments() {
+            for base in segment {
+                match base.to_ascii_uppercase() {
+                    b'A' => counts.a += 1,
+                    b'C' => counts.c += 1,
+                    b'G' => counts.g += 1,
+                    b'T' => counts.t += 1,
+                    b'N' => counts.n += 1,
+                    _ => counts.other += 1,
+                }
+            }
+        }
+        counts
+    }
+
+    /// Returns the melting temperature of a short DNA oligonucleotide in °C,
+    /// estimated with the Wallace rule: 2 °C per `A` or `T`, and 4 °C per `G` or `C`.
+    /// Bases are counted case-insensitively, skipping newlines.
+    ///
+    /// The rule is only a rough estimate for primers shorter than 14 bases,
+    /// so `None` is returned for longer and empty sequences,
+    /// and for sequences that contain anything but `A`, `C`, `G`, and `T`.
+    /// See [`melting_temp_nn`] for a more accurate estimate.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">primer\nACGTT\nGCA")?;
+    /// assert_eq!(fasta.sequences[0].melting_temp_wallace(), Some(24.0));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`melting_temp_nn`]: FastaSequence::melting_temp_nn
+    pub fn melting_temp_wallace(&self) -> Option<f64> {
+        let counts = self.base_counts();
+        let len = counts.a + counts.c + counts.g + counts.t;
+        if counts.n + counts.other > 0 || !(1..WALLACE_MAX_LEN).contains(&len) {
+            return None;
+        }
+
+        Some((2 * (counts.a + counts.t) + 4 * (counts.g + counts.c)) as f64)
+    }
+
+    /// Returns the melting temperature of a DNA oligonucleotide in °C,
+    /// estimated with the nearest-neighbor model and the unified thermodynamic parameters of
+    /// SantaLucia (1998).
+    /// Bases are read case-insensitively, skipping newlines.
+    ///
+    /// The temperature is computed as `ΔH / (ΔS + R ln(C / x)) - 273.15`,
+    /// where `ΔH` and `ΔS` are the sums of the enthalpies and entropies of the base stacks,
+    /// the initiation terms for the terminal bases, and, for self-complementary sequences,
+    /// the symmetry correction.
+    /// The entropy is corrected for a sodium concentration of 50 mM
+    /// (`0.368 (N - 1) ln [Na+]`).
+    /// `C` is an oligonucleotide concentration of 0.25 µM, and `x` is `4`,
+    /// or `1` for self-complementary sequences.
+    ///
+    /// The model is only valid for oligonucleotides of 8 to 60 bases,
+    /// so `None` is returned for shorter and longer sequences,
+    /// and for sequences that contain anything but `A`, `C`, `G`, and `T`.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">M13 reverse\nAGCGGATAAC\nAATTTCACACAGGA")?;
+    /// let tm = fasta.sequences[0].melting_temp_nn().unwrap();
+    /// assert!((tm - 56.7).abs() < 0.1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn melting_temp_nn(&self) -> Option<f64> {
+        let len = self.len();
+        if !(NN_MIN_LEN..=NN_MAX_LEN).contains(&len) {
+            return None;
+        }
+
+        let bases = self.iter_uppercase().collect::<Vec<_>>();
+        if !bases.iter().all(|base| b"ACGT".contains(base)) {
+            return None;
+        }
+
+        let (mut enthalpy, mut entropy) = (0.0, 0.0);
+        for stack in bases.windows(2) {
+            let (h, s) = nearest_neighbor(stack[0], stack[1]);
+            enthalpy += h;
+            entropy += s;
+        }
+        for terminal in [bases[0], bases[len - 1]] {
+            let (h, s) = match terminal {
+                b'G' | b'C' => (0.1, -2.8),
+                _ => (2.3, 4.1),
+            };
+            enthalpy += h;
+            entropy += s;
+        }
+
+        let symmetric = self.is_reverse_complement_palindrome(&ComplementTable::dna());
+        if symmetric {
+            entropy -= 1.4;
+        }
+        entropy += 0.368 * (len - 1) as f64 * NN_SODIUM.ln();
+
+        let concentration = NN_OLIGO_CONCENTRATION / if symmetric { 1.0 } else { 4.0 };
+        Some(enthalpy * 1000.0 / (entropy + GAS_CONSTANT * concentration.ln()) - 273.15)
+    }
+
+    /// Returns the residue and the length of the longest homopolymer run of the sequence,
+    /// i.e. the longest stretch of one repeated residue.
+    /// Residues are compared exactly, so `A` and `a` are different residues.
+    /// Newlines are skipped, so runs may span line breaks.
+    /// If multiple runs have the same length, the first one is returned.
+    ///
+    /// Returns `(0, 0)` for an empty sequence.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">seq\nACCCGT\nTTTTA")?;
+    /// assert_eq!(fasta.sequences[0].longest_run(), (b'T', 5));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn longest_run(&self) -> (u8, usize) {
+        let mut longest = (0, 0);
+        let mut current = (0, 0);
+        for &c in self.iter() {
+            if current.1 > 0 && current.0 == c {
+                current.1 += 1;
+            } else {
+                current = (c, 1);
+            }
+            if current.1 > longest.1 {
+                longest = current;
+            }
+        }
+        longest
+    }
+
+    /// Returns the length of the longest run of unknown bases (`N` or `n`) of the sequence,
+    /// which usually marks a gap between the contigs of a scaffold.
+    /// Newlines are skipped, so runs may span line breaks.
+    pub fn longest_n_run(&self) -> usize {
+        let mut longest = 0;
+        let mut current = 0;
+        for c in self.iter() {
+            if c.eq_ignore_ascii_case(&b'N') {
+                current += 1;
+                longest = longest.max(current);
+            } else {
+                current = 0;
+            }
+        }
+        longest
+    }
+
+    /// Pack the DNA sequence into 2 bits per base, skipping newlines,
+    /// for compact storage of sequences that only contain the bases `A`, `C`, `G`, and `T`.
+    /// Lowercase bases are packed like uppercase bases, so soft-masking is lost.
+    ///
+    /// The bases are encoded as `A = 00`, `C = 01`, `G = 10`, and `T = 11`,
+    /// and four bases are packed into each byte, with the first base in the most significant bits.
+    /// The unused bits of the last byte are zero.
+    /// Use [`unpack_2bit`] with the returned base count to restore the sequence.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use fire_fasta::nucleotide::unpack_2bit;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">seq\nACGT\nTG")?;
+    /// let (packed, len) = fasta.sequences[0].pack_2bit()?;
+    ///
+    /// assert_eq!(packed, vec![0b00_01_10_11, 0b11_10_00_00]);
+    /// assert_eq!(unpack_2bit(&packed, len), b"ACGTTG");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an [`InvalidBase`] error for the first character that is not a base,
+    /// including unknown bases (`N`).
+    ///
+    /// [`InvalidBase`]: PackError::InvalidBase
+    pub fn pack_2bit(&self) -> Result<(Vec<u8>, usize), PackError> {
+        let mut packed = Vec::with_capacity(self.size_hint().div_ceil(4));
+        let mut len = 0;
+        for &byte in self.iter() {
+            let code = match byte.to_ascii_uppercase() {
+                b'A' => 0b00,
+                b'C' => 0b01,
+                b'G' => 0b10,
+                b'T' => 0b11,
+                _ => {
+                    return Err(PackError::InvalidBase {
+                        byte,
+                        position: len,
+                    })
+                }
+            };
+
+            if len % 4 == 0 {
+                packed.push(0);
+            }
+            *packed.last_mut().unwrap() |= code << (6 - 2 * (len % 4));
+            len += 1;
+        }
+        Ok((packed, len))
+    }
+
+    /// Returns the complement of the sequence in its original order, skipping newlines,
+    /// i.e. the opposite strand read from 3' to 5'.
+    /// Bases are complemented on the fly while copying,
+    /// and bytes without a complement are copied unchanged (see [`ComplementTable`]).
+    /// See [`reverse_complement`] for the opposite strand in 5' to 3' order.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use fire_fasta::nucleotide::ComplementTable;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">seq\nAACG\nTTGA")?;
+    /// let table = ComplementTable::dna();
+    /// assert_eq!(fasta.sequences[0].complement(&table).as_ref(), b"TTGCAACT");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`reverse_complement`]: FastaSequence::reverse_complement
+    #[must_use]
+    pub fn complement(&self, table: &ComplementTable) -> Box<[u8]> {
+        let mut buffer = Vec::with_capacity(self.size_hint());
+        for segment in self.segments() {
+            buffer.extend(segment.iter().map(|&base| table.complement(base)));
+        }
+        buffer.into_boxed_slice()
+    }
+
+    /// Returns the reverse complement of the sequence, skipping newlines.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use fire_fasta::nucleotide::ComplementTable;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">seq\nAACG\nTTGA")?;
+    /// let table = ComplementTable::dna();
+    /// assert_eq!(fasta.sequences[0].reverse_complement(&table).as_ref(), b"TCAACGTT");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn reverse_complement(&self, table: &ComplementTable) -> Box<[u8]> {
+        self.subsequence_rc(0, usize::MAX, table)
+    }
+
+    /// Returns true if the sequence is equal to its own reverse complement, skipping newlines,
+    /// like the recognition sites of many restriction enzymes (e.g. `GAATTC`).
+    /// The sequence is compared from both ends at once, without allocating the reverse
+    /// complement.
+    /// Since the table preserves case, bases are compared case-sensitively.
+    ///
+    /// Under this definition, sequences of odd length are never palindromes,
+    /// even if the middle base is its own complement (such as `N` in `ANT`),
+    /// and the empty sequence is a palindrome.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use fire_fasta::nucleotide::ComplementTable;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">EcoRI\nGAA\nTTC\n>other\nGAATTA")?;
+    /// let table = ComplementTable::dna();
+    ///
+    /// assert!(fasta.sequences[0].is_reverse_complement_palindrome(&table));
+    /// assert!(!fasta.sequences[1].is_reverse_complement_palindrome(&table));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn is_reverse_complement_palindrome(&self, table: &ComplementTable) -> bool {
+        // both iterators yield references into the same input, so their addresses show where
+        // they meet without counting the residues first
+        let mut forward = self.iter();
+        let mut backward = self.iter_rev();
+        while let (Some(front), Some(back)) = (forward.next(), backward.next()) {
+            let (front_ptr, back_ptr) = (ptr::from_ref(front), ptr::from_ref(back));
+            if front_ptr > back_ptr {
+                // the iterators crossed between two residues, so the length is even
+                return true;
+            }
+            if front_ptr == back_ptr || *front != table.complement(*back) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Returns the reverse complement of the residues in `[start, end)`, skipping newlines,
+    /// as needed for features on the reverse strand.
+    /// The residues are complemented directly into the result buffer, without an intermediate copy.
+    /// An `end` past the end of the sequence is clamped,
+    /// and if `start >= end`, the result is empty.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use fire_fasta::nucleotide::ComplementTable;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">seq\nAACG\nTTGA")?;
+    /// let table = ComplementTable::dna();
+    ///
+    /// assert_eq!(fasta.sequences[0].subsequence_rc(1, 6, &table).as_ref(), b"AACGT");
+    /// assert_eq!(fasta.sequences[0].subsequence_rc(6, 100, &table).as_ref(), b"TC");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn subsequence_rc(&self, start: usize, end: usize, table: &ComplementTable) -> Box<[u8]> {
+        let end = end.min(self.len());
+        if start >= end {
+            return Box::default();
+        }
+
+        let mut buffer = vec![0; end - start].into_boxed_slice();
+        let mut out = buffer.len();
+        let mut offset = 0;
+        for segment in self.segments() {
+            let segment_start = offset;
+            offset += segment.len();
+            if offset <= start {
+                continue;
+            }
+
+            let from = start.saturating_sub(segment_start);
+            let to = (end - segment_start).min(segment.len());
+            for &base in &segment[from..to] {
+                out -= 1;
+                buffer[out] = table.complement(base);
+            }
+
+            if offset >= end {
+                break;
+            }
+        }
+
+        buffer
+    }
+
+    /// Copy the sequence into a consecutive buffer, skipping newlines, and mask the residues in
+    /// the given ranges, as needed for repeat-masked or feature-masked references.
+    /// The ranges are residue positions, excluding newlines.
+    /// Depending on `mode`, masked residues are replaced with `N` or with their lowercase form.
+    ///
+    /// The ranges may overlap and may be given in any order.
+    /// Ranges past the end of the sequence are clamped, and empty ranges are ignored.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use fire_fasta::nucleotide::MaskMode;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">seq\nAACG\nTTGA")?;
+    /// let seq = &fasta.sequences[0];
+    ///
+    /// assert_eq!(seq.masked_copy(&[1..3, 6..100], MaskMode::Hard).as_ref(), b"ANNGTTNN");
+    /// assert_eq!(seq.masked_copy(&[2..5, 1..3], MaskMode::Soft).as_ref(), b"AacgtTGA");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn masked_copy(&self, ranges: &[Range<usize>], mode: MaskMode) -> Box<[u8]> {
+        let mut buffer = self.copy_sequential();
+        let len = buffer.len();
+
+        for range in ranges {
+            let end = range.end.min(len);
+            let Some(masked) = buffer.get_mut(range.start..end) else {
+                continue;
+            };
+
+            match mode {
+                MaskMode::Hard => masked.fill(b'N'),
+                MaskMode::Soft => masked.make_ascii_lowercase(),
+            }
+        }
+
+        buffer
+    }
+}
+
+impl<'a> Fasta<'a> {
+    /// Create a database that contains the reverse complement of every sequence,
+    /// i.e. the opposite strand of the whole file.
+    /// If `annotate` is true, ` (revcomp)` is appended to each description,
+    /// otherwise the descriptions are copied unchanged.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use fire_fasta::nucleotide::ComplementTable;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">chr1\nAACG\nTT")?;
+    /// let minus = fasta.reverse_complement_all(&ComplementTable::dna(), true);
+    ///
+    /// assert_eq!(minus.sequences()[0].description(), b"chr1 (revcomp)");
+    /// assert_eq!(minus.sequences()[0].sequence(), b"AACGTT");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn reverse_complement_all(&self, table: &ComplementTable, annotate: bool) -> OwnedFasta {
+        self.sequences
+            .iter()
+            .map(|seq| {
+                let mut description = seq.description.to_vec();
+                if annotate {
+                    description.extend_from_slice(b" (revcomp)");
+                }
+                OwnedFastaSequence::new(description, seq.reverse_complement(table))
+            })
+            .collect()
+    }
+}
+
+/// The length from which the Wallace rule is no longer used.
+const WALLACE_MAX_LEN: usize = 14;
+
+/// The range of lengths the nearest-neighbor model is used for.
+const NN_MIN_LEN: usize = 8;
+const NN_MAX_LEN: usize = 60;
+
+/// The sodium concentration of the nearest-neighbor model in mol/l.
+const NN_SODIUM: f64 = 0.05;
+
+/// The oligonucleotide concentration of the nearest-neighbor model in mol/l.
+const NN_OLIGO_CONCENTRATION: f64 = 0.25e-6;
+
+/// The gas constant in cal/(K mol).
+const GAS_CONSTANT: f64 = 1.9872;
+
+/// Returns the enthalpy (kcal/mol) and entropy (cal/(K mol)) of the stack of the uppercase bases
+/// `a` and `b` on one strand, from the unified parameters of SantaLucia (1998).
+fn nearest_neighbor(a: u8, b: u8) -> (f64, f64) {
+    match [a, b] {
+        [b'A', b'A'] | [b'T', b'T'] => (-7.9, -22.2),
+        [b'A', b'T'] => (-7.2, -20.4),
+        [b'T', b'A'] => (-7.2, -21.3),
+        [b'C', b'A'] | [b'T', b'G'] => (-8.5, -22.7),
+        [b'G', b'T'] | [b'A', b'C'] => (-8.4, -22.4),
+        [b'C', b'T'] | [b'A', b'G'] => (-7.8, -21.0),
+        [b'G', b'A'] | [b'T', b'C'] => (-8.2, -22.2),
+        [b'C', b'G'] => (-10.6, -27.2),
+        [b'G', b'C'] => (-9.8, -24.4),
+        [b'G', b'G'] | [b'C', b'C'] => (-8.0, -19.9),
+        _ => unreachable!("not a DNA base stack"),
+    }
+}
+
+/// Unpack the first `len` bases of a sequence packed by [`FastaSequence::pack_2bit`]
+/// into uppercase bases.
+///
+/// # Panics
+/// Panics if `packed` contains less than `len` bases, i.e. if it is shorter than `len / 4`
+/// bytes, rounded up.
+pub fn unpack_2bit(packed: &[u8], len: usize) -> Vec<u8> {
+    assert!(
+        packed.len() >= len.div_ceil(4),
+        "packed sequence is shorter than {len} bases"
+    );
+    (0..len)
+        .map(|i| b"ACGT"[usize::from(packed[i / 4] >> (6 - 2 * (i % 4)) & 0b11)])
+        .collect()
+}
+
+/// Replace all occurrences of the uppercase base `from` with `to`, and the lowercase form of `from`
+/// with the lowercase form of `to`.
+fn replace_base(buffer: &mut [u8], from: u8, to: u8) {
+    let lower_from = from.to_ascii_lowercase();
+    let lower_to = to.to_ascii_lowercase();
+    for c in buffer.iter_mut() {
+        if *c == from {
+            *c = to;
+        } else if *c == lower_from {
+            *c = lower_to;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,255 @@
+use crate::nucleotide::{unpack_2bit, BaseCounts, ComplementTable, MaskMode, PackError};
+use crate::{parse_fasta, OwnedFastaSequence};
+use std::ops::Range;
+
+#[test]
+fn test_transcription() {
+    let fasta =
+        parse_fasta(b">dna\nATGC\nTTNa\nt-\n>rna\nAUGC\nUUNa\nu-").expect("Failed to parse FASTA");
+
+    assert_eq!(fasta.sequences[0].to_rna().as_ref(), b"AUGCUUNau-");
+    assert_eq!(fasta.sequences[1].to_dna().as_ref(), b"ATGCTTNat-");
+    assert_eq!(fasta.sequences[0].to_dna().as_ref(), b"ATGCTTNat-");
+}
+
+#[test]
+fn test_codons() {
+    let fasta = parse_fasta(b">seq\nAT\nGC\nC\nATT\nAG\n").expect("Failed to parse FASTA");
+    let seq = &fasta.sequences[0];
+
+    assert_eq!(
+        seq.codons(0).collect::<Vec<_>>(),
+        vec![*b"ATG", *b"CCA", *b"TTA"]
+    );
+    assert_eq!(
+        seq.codons(1).collect::<Vec<_>>(),
+        vec![*b"TGC", *b"CAT", *b"TAG"]
+    );
+    assert_eq!(seq.codons(2).collect::<Vec<_>>(), vec![*b"GCC", *b"ATT"]);
+    assert_eq!(seq.codons(10).count(), 0);
+}
+
+#[test]
+fn test_complement_table() {
+    let dna = ComplementTable::dna();
+    let complement = b"ACGTUNRYKMSWBDHVacgtun-*".map(|base| dna.complement(base));
+    assert_eq!(&complement, b"TGCAANYRMKSWVHDBtgcaan-*");
+
+    let rna = ComplementTable::rna();
+    let complement = b"ACGUTacgut".map(|base| rna.complement(base));
+    assert_eq!(&complement, b"UGCAAugcaa");
+}
+
+#[test]
+fn test_subsequence_rc() {
+    let fasta = parse_fasta(b">seq\nAC\nGTT\n\nGCA\n").expect("Failed to parse FASTA");
+    let seq = &fasta.sequences[0];
+    let table = ComplementTable::dna();
+
+    let residues = seq.copy_sequential();
+    for start in 0..=residues.len() + 1 {
+        for end in 0..=residues.len() + 1 {
+            let range = start.min(residues.len())..end.min(residues.len());
+            let expected = residues
+                .get(range)
+                .unwrap_or_default()
+                .iter()
+                .rev()
+                .map(|&base| table.complement(base))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                seq.subsequence_rc(start, end, &table).as_ref(),
+                expected,
+                "{start}..{end}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_base_counts() {
+    let fasta = parse_fasta(b">a\nAaCc\nGTtn\n\nU-*\n>b\n\n").expect("Failed to parse FASTA");
+
+    let counts = fasta.sequences[0].base_counts();
+    assert_eq!(
+        counts,
+        BaseCounts {
+            a: 2,
+            c: 2,
+            g: 1,
+            t: 2,
+            n: 1,
+            other: 3,
+        }
+    );
+    assert_eq!(counts.gc_content(), 3.0 / 7.0);
+
+    let counts = fasta.sequences[1].base_counts();
+    assert_eq!(counts, BaseCounts::default());
+    assert_eq!(counts.gc_content(), 0.0);
+}
+
+#[test]
+fn test_longest_runs() {
+    let fasta = parse_fasta(b">a\nAAcc\nCCCg\n\nNNn\nNaaaa\n>b\nACGT\n>c\n\n")
+        .expect("Failed to parse FASTA");
+
+    assert_eq!(fasta.sequences[0].longest_run(), (b'a', 4));
+    assert_eq!(fasta.sequences[0].longest_n_run(), 4);
+    assert_eq!(fasta.sequences[1].longest_run(), (b'A', 1));
+    assert_eq!(fasta.sequences[1].longest_n_run(), 0);
+    assert_eq!(fasta.sequences[2].longest_run(), (0, 0));
+}
+
+#[test]
+fn test_reverse_complement_all() {
+    let fasta = parse_fasta(b">a x\nACG\nTTn\n>b\n\n>c\nAUG").expect("Failed to parse FASTA");
+
+    let minus = fasta.reverse_complement_all(&ComplementTable::dna(), false);
+    assert_eq!(
+        minus.sequences(),
+        &[
+            OwnedFastaSequence::new(b"a x".to_vec(), b"nAACGT".to_vec()),
+            OwnedFastaSequence::new(b"b".to_vec(), b"".to_vec()),
+            OwnedFastaSequence::new(b"c".to_vec(), b"CAT".to_vec()),
+        ]
+    );
+
+    let minus = fasta.reverse_complement_all(&ComplementTable::rna(), true);
+    assert_eq!(minus.sequences()[0].description(), b"a x (revcomp)");
+    assert_eq!(minus.sequences()[2].sequence(), b"CAU");
+}
+
+#[test]
+fn test_pack_2bit() {
+    let fasta = parse_fasta(b">a\nACGTa\ncg\n\nt\n>b\n\n>c\nACNT").expect("Failed to parse FASTA");
+
+    let (packed, len) = fasta.sequences[0].pack_2bit().unwrap();
+    assert_eq!(len, 8);
+    assert_eq!(packed, vec![0b00_01_10_11, 0b00_01_10_11]);
+    assert_eq!(unpack_2bit(&packed, len), b"ACGTACGT");
+    assert_eq!(unpack_2bit(&packed, 5), b"ACGTA");
+
+    let (packed, len) = fasta.sequences[1].pack_2bit().unwrap();
+    assert!(packed.is_empty());
+    assert_eq!(len, 0);
+
+    assert!(matches!(
+        fasta.sequences[2].pack_2bit(),
+        Err(PackError::InvalidBase {
+            byte: b'N',
+            position: 2
+        })
+    ));
+    assert_eq!(
+        fasta.sequences[2].pack_2bit().unwrap_err().to_string(),
+        "invalid base 'N' (byte 0x4E) at position 2; only A, C, G, and T can be packed"
+    );
+}
+
+#[test]
+#[should_panic(expected = "shorter than")]
+fn test_unpack_2bit_too_short() {
+    let _ = unpack_2bit(&[0], 5);
+}
+
+#[test]
+fn test_masked_copy() {
+    let fasta = parse_fasta(b">a\nACGT\nacgt\n\nAC\n>b\n\n").expect("Failed to parse FASTA");
+    let seq = &fasta.sequences[0];
+
+    assert_eq!(seq.masked_copy(&[], MaskMode::Hard).as_ref(), b"ACGTacgtAC");
+    assert_eq!(
+        seq.masked_copy(&[3..5, 9..20, 4..6], MaskMode::Hard)
+            .as_ref(),
+        b"ACGNNNgtAN"
+    );
+    assert_eq!(
+        seq.masked_copy(
+            &[0..2, 8..8, Range { start: 6, end: 4 }, 20..30],
+            MaskMode::Soft
+        )
+        .as_ref(),
+        b"acGTacgtAC"
+    );
+    assert!(fasta.sequences[1]
+        .masked_copy(&[0..10, 5..20], MaskMode::Hard)
+        .is_empty());
+}
+
+#[test]
+fn test_complement() {
+    let fasta = parse_fasta(b">a\nACgt\nN-u\n\nR\n>b\n\n").expect("Failed to parse FASTA");
+    let seq = &fasta.sequences[0];
+
+    assert_eq!(
+        seq.complement(&ComplementTable::dna()).as_ref(),
+        b"TGcaN-aY"
+    );
+    assert_eq!(
+        seq.complement(&ComplementTable::rna()).as_ref(),
+        b"UGcaN-aY"
+    );
+    let mut reversed = seq.complement(&ComplementTable::dna());
+    reversed.reverse();
+    assert_eq!(reversed, seq.reverse_complement(&ComplementTable::dna()));
+    assert!(fasta.sequences[1]
+        .complement(&ComplementTable::dna())
+        .is_empty());
+}
+
+#[test]
+fn test_reverse_complement_palindrome() {
+    let fasta = parse_fasta(b">a\nGA\n\nATTC\n>b\nGAATC\n>c\n\n>d\ngaAT\nTc\n>e\nACGA\n>f\nAUGCAU")
+        .expect("Failed to parse FASTA");
+    let dna = ComplementTable::dna();
+
+    let palindromes = fasta
+        .sequences
+        .iter()
+        .map(|seq| seq.is_reverse_complement_palindrome(&dna))
+        .collect::<Vec<_>>();
+    assert_eq!(palindromes, vec![true, false, true, false, false, false]);
+    assert!(fasta.sequences[5].is_reverse_complement_palindrome(&ComplementTable::rna()));
+
+    // the middle base of an odd-length sequence has no partner, even if it is its own complement
+    let odd = parse_fasta(b">a\nANT\n>b\nAS\nT\n>c\nW\n>d\nG.C").expect("Failed to parse FASTA");
+    assert!(odd
+        .iter()
+        .all(|seq| !seq.is_reverse_complement_palindrome(&dna)));
+}
+
+#[test]
+fn test_melting_temp_wallace() {
+    let tm = |data: &[u8]| {
+        parse_fasta(data).expect("Failed to parse FASTA").sequences[0].melting_temp_wallace()
+    };
+
+    assert_eq!(tm(b">a\nA"), Some(2.0));
+    assert_eq!(tm(b">a\nacg\nT\n"), Some(12.0));
+    assert_eq!(tm(b">a\nGGGGGGGGGGGGG"), Some(52.0));
+    assert_eq!(tm(b">a\nGGGGGGGGGGGGGG"), None);
+    assert_eq!(tm(b">a\nACGN"), None);
+    assert_eq!(tm(b">a\nACGU"), None);
+    assert_eq!(tm(b">a\n\n>b\nA"), None);
+}
+
+#[test]
+fn test_melting_temp_nn() {
+    let tm = |data: &[u8]| {
+        parse_fasta(data).expect("Failed to parse FASTA").sequences[0].melting_temp_nn()
+    };
+    let close = |tm: Option<f64>, expected: f64| tm.is_some_and(|tm| (tm - expected).abs() < 0.01);
+
+    // reference values computed from the published parameters
+    assert!(close(tm(b">a\nACGTACGTAC\nGTACGTACGT"), 56.13));
+    assert!(close(tm(b">a\nagcggataacaatttcacacagga"), 56.71));
+    // the reverse complement forms the same duplex
+    assert!(close(tm(b">a\nTCCTGTGTGAAATTGTTATCCGCT"), 56.71));
+
+    assert!(tm(b">a\nACGTACG").is_none());
+    assert!(tm(b">a\nACGTACGT").is_some());
+    assert!(tm(&[&b">a\n"[..], &[b'A'; 60]].concat()).is_some());
+    assert!(tm(&[&b">a\n"[..], &[b'A'; 61]].concat()).is_none());
+    assert!(tm(b">a\nACGTACGTN").is_none());
+}
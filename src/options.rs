@@ -0,0 +1,260 @@
+/// Options to configure the parsing behavior of [`parse_fasta_with`].
+/// The [`Default`] options parse exactly like [`parse_fasta`].
+/// Options can be set through the public fields, or with the builder-style setters,
+/// so only the options that differ from the default need to be configured:
+///
+/// ```rust
+/// # use fire_fasta::{parse_fasta_with, ParseOptions};
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let options = ParseOptions::new().max_records(1);
+///
+/// let fasta = parse_fasta_with(b">Sample1\nACGTCA\n>Sample2\nACGTCC", options)?;
+/// assert_eq!(fasta.sequences.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`parse_fasta_with`]: crate::parse_fasta_with
+/// [`parse_fasta`]: crate::parse_fasta
+#[derive(Clone, Copy, Debug, Default)]
+#[non_exhaustive]
+pub struct ParseOptions {
+    /// The maximum number of records to parse.
+    /// If the input contains more records, the parser stops after the limit is reached,
+    /// without scanning the rest of the input.
+    /// Whether the parser returns the records parsed so far or an error is controlled by
+    /// [`error_on_max_records`].
+    ///
+    /// Defaults to `None`, meaning all records are parsed.
+    ///
+    /// [`error_on_max_records`]: ParseOptions::error_on_max_records
+    pub max_records: Option<usize>,
+
+    /// Whether the parser returns a [`TooManyRecords`] error if the input contains more records
+    /// than [`max_records`], instead of returning the records parsed until the limit was hit.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [`TooManyRecords`]: crate::ParseError::TooManyRecords
+    /// [`max_records`]: ParseOptions::max_records
+    pub error_on_max_records: bool,
+
+    /// The newline convention of the input.
+    ///
+    /// Defaults to [`Newline::Lf`].
+    pub newline: Newline,
+
+    /// Whether blank lines (and any other ASCII whitespace) before the first description are
+    /// skipped, instead of failing with an [`InvalidDescription`] error.
+    /// An input consisting only of whitespace is parsed as an empty file.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [`InvalidDescription`]: crate::ParseError::InvalidDescription
+    pub skip_leading_blank_lines: bool,
+
+    /// Which `>` characters start a new record.
+    ///
+    /// Defaults to [`RecordStart::AnyGreaterThan`].
+    pub record_start: RecordStart,
+
+    /// Whether bytes before the first description are parsed as an anonymous sequence with an
+    /// empty description, instead of failing with an [`InvalidDescription`] error.
+    /// This allows parsing chunks of a file that was split at arbitrary byte offsets,
+    /// where a chunk may start in the middle of a sequence.
+    /// The first record of such a chunk has an empty description,
+    /// and needs to be stitched to the last record of the previous chunk by the caller.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [`InvalidDescription`]: crate::ParseError::InvalidDescription
+    pub assume_leading_sequence: bool,
+
+    /// The maximum length of a description line in bytes, excluding the `>` and the newline.
+    /// A longer description fails with a [`HeaderTooLong`] error,
+    /// which guards against untrusted input, such as a binary file without newlines,
+    /// where all of the input would be treated as a single description.
+    /// The newline is only searched within the allowed length.
+    ///
+    /// Defaults to `None`, meaning descriptions can have any length.
+    ///
+    /// [`HeaderTooLong`]: crate::ParseError::HeaderTooLong
+    pub max_header_len: Option<usize>,
+
+    /// Whether leading and trailing ASCII whitespace, such as trailing spaces or a stray `CR`,
+    /// is trimmed from each description, so IDs can be matched exactly.
+    /// Trimming only narrows the description slice, so it doesn't allocate.
+    ///
+    /// Defaults to `false`, so descriptions are byte-faithful to the input.
+    pub trim_descriptions: bool,
+
+    /// Whether records are separated by blank lines instead of starting with `>`,
+    /// a dialect used by some nonstandard exports.
+    /// In this mode, the first line of each block of non-blank lines is the description of a
+    /// record, and the remaining lines of the block are its sequence.
+    /// The block may be followed by any number of blank lines, which are included in the raw
+    /// sequence like trailing newlines.
+    /// A block consisting only of a description is a record with an empty sequence.
+    ///
+    /// The `>`-based and the blank-line-based formats are mutually exclusive:
+    /// In this mode, `>` has no special meaning and is kept as part of the description or the
+    /// sequence, and [`record_start`] and [`assume_leading_sequence`] are ignored.
+    /// Blank lines before the first record are always skipped.
+    /// In [`Newline::Auto`] mode, a line consisting of a single `CR` is blank as well.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [`record_start`]: ParseOptions::record_start
+    /// [`assume_leading_sequence`]: ParseOptions::assume_leading_sequence
+    pub blank_line_separator: bool,
+
+    /// Whether spaces and tabs in sequences are skipped like newlines,
+    /// for files that separate blocks of residues on a line by spaces (`ACG TAC\nGGG`).
+    /// The raw sequence still contains the spaces, so they are skipped on the fly when iterating
+    /// or copying the sequence, just like newlines.
+    ///
+    /// Defaults to `false`, so spaces and tabs are preserved as sequence characters.
+    pub strip_sequence_spaces: bool,
+}
+
+/// The newline convention used to separate lines of the input.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Newline {
+    /// Unix-style newlines (`LF`).
+    /// Carriage returns (`CR`) are preserved as regular sequence characters.
+    #[default]
+    Lf,
+
+    /// Best-effort mode for Windows-style (`CRLF`) and mixed newlines.
+    /// A `CR` immediately preceding a `LF` is treated as part of the newline,
+    /// and is stripped from descriptions and skipped when iterating or copying sequences.
+    /// Any other `CR` is preserved as a regular sequence character.
+    Auto,
+}
+
+/// The rule for recognizing the start of the next record while scanning a sequence.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RecordStart {
+    /// Any `>` ends the current sequence, even in the middle of a line.
+    /// This is the fastest scan, and correct for any input that doesn't contain `>` in sequences.
+    #[default]
+    AnyGreaterThan,
+
+    /// Only a `>` at the beginning of a line ends the current sequence,
+    /// as required by the FASTA format.
+    /// A `>` anywhere else is kept as a regular sequence character.
+    LineStart,
+}
+
+impl ParseOptions {
+    /// Create the default options, which parse exactly like [`parse_fasta`].
+    ///
+    /// [`parse_fasta`]: crate::parse_fasta
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create options that follow the FASTA format strictly,
+    /// at a small performance cost compared to the default options.
+    /// Records only start at a `>` at the beginning of a line (see [`RecordStart::LineStart`]).
+    pub fn strict() -> Self {
+        Self {
+            record_start: RecordStart::LineStart,
+            ..Self::default()
+        }
+    }
+
+    /// Set [`max_records`] to the given limit.
+    ///
+    /// [`max_records`]: ParseOptions::max_records
+    #[must_use]
+    pub fn max_records(mut self, limit: usize) -> Self {
+        self.max_records = Some(limit);
+        self
+    }
+
+    /// Set whether the parser fails if the input contains more records than allowed
+    /// (see [`error_on_max_records`]).
+    ///
+    /// [`error_on_max_records`]: ParseOptions::error_on_max_records
+    #[must_use]
+    pub fn error_on_max_records(mut self, error: bool) -> Self {
+        self.error_on_max_records = error;
+        self
+    }
+
+    /// Set the newline convention of the input (see [`newline`]).
+    ///
+    /// [`newline`]: ParseOptions::newline
+    #[must_use]
+    pub fn newline(mut self, newline: Newline) -> Self {
+        self.newline = newline;
+        self
+    }
+
+    /// Set whether blank lines before the first description are skipped
+    /// (see [`skip_leading_blank_lines`]).
+    ///
+    /// [`skip_leading_blank_lines`]: ParseOptions::skip_leading_blank_lines
+    #[must_use]
+    pub fn skip_leading_blank_lines(mut self, skip: bool) -> Self {
+        self.skip_leading_blank_lines = skip;
+        self
+    }
+
+    /// Set which `>` characters start a new record (see [`record_start`]).
+    ///
+    /// [`record_start`]: ParseOptions::record_start
+    #[must_use]
+    pub fn record_start(mut self, record_start: RecordStart) -> Self {
+        self.record_start = record_start;
+        self
+    }
+
+    /// Set whether bytes before the first description are parsed as a sequence
+    /// (see [`assume_leading_sequence`]).
+    ///
+    /// [`assume_leading_sequence`]: ParseOptions::assume_leading_sequence
+    #[must_use]
+    pub fn assume_leading_sequence(mut self, assume: bool) -> Self {
+        self.assume_leading_sequence = assume;
+        self
+    }
+
+    /// Set [`max_header_len`] to the given limit.
+    ///
+    /// [`max_header_len`]: ParseOptions::max_header_len
+    #[must_use]
+    pub fn max_header_len(mut self, limit: usize) -> Self {
+        self.max_header_len = Some(limit);
+        self
+    }
+
+    /// Set whether whitespace is trimmed from descriptions (see [`trim_descriptions`]).
+    ///
+    /// [`trim_descriptions`]: ParseOptions::trim_descriptions
+    #[must_use]
+    pub fn trim_descriptions(mut self, trim: bool) -> Self {
+        self.trim_descriptions = trim;
+        self
+    }
+
+    /// Set whether records are separated by blank lines (see [`blank_line_separator`]).
+    ///
+    /// [`blank_line_separator`]: ParseOptions::blank_line_separator
+    #[must_use]
+    pub fn blank_line_separator(mut self, blank_line_separator: bool) -> Self {
+        self.blank_line_separator = blank_line_separator;
+        self
+    }
+
+    /// Set whether spaces and tabs in sequences are skipped (see [`strip_sequence_spaces`]).
+    ///
+    /// [`strip_sequence_spaces`]: ParseOptions::strip_sequence_spaces
+    #[must_use]
+    pub fn strip_sequence_spaces(mut self, strip: bool) -> Self {
+        self.strip_sequence_spaces = strip;
+        self
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{Fasta, FastaSequence};
+
+/// A Multi FASTA database that owns its sequences.
+/// In contrast to [`Fasta`], it does not borrow from the parsed input, and can thus be edited and
+/// kept around after the input buffer is dropped.
+///
+/// ```rust
+/// # use fire_fasta::{parse_fasta, OwnedFasta};
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let fasta = parse_fasta(b">Sample1\nACGT\nCA\n>Sample2\nACGTCC")?;
+/// let owned = OwnedFasta::from(&fasta);
+///
+/// assert_eq!(owned.sequences()[0].description(), b"Sample1");
+/// assert_eq!(owned.sequences()[0].sequence(), b"ACGTCA");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OwnedFasta {
+    sequences: Vec<OwnedFastaSequence>,
+}
+
+/// A FASTA sequence with a description that owns its data.
+/// Unlike [`FastaSequence`], the sequence is stored without newlines,
+/// so it can be accessed as a contiguous slice.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedFastaSequence {
+    description: Vec<u8>,
+    sequence: Vec<u8>,
+}
+
+impl OwnedFasta {
+    /// Create an empty database.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the sequences of the database.
+    pub fn sequences(&self) -> &[OwnedFastaSequence] {
+        &self.sequences
+    }
+
+    /// Returns the number of sequences in the database.
+    pub fn len(&self) -> usize {
+        self.sequences.len()
+    }
+
+    /// Returns true if the database contains no sequences.
+    pub fn is_empty(&self) -> bool {
+        self.sequences.is_empty()
+    }
+
+    /// Append a sequence to the end of the database.
+    pub fn push(&mut self, sequence: OwnedFastaSequence) {
+        self.sequences.push(sequence);
+    }
+
+    /// Retain only the sequences for which the predicate returns true,
+    /// preserving the order of the retained sequences.
+    pub fn retain<F: FnMut(&OwnedFastaSequence) -> bool>(&mut self, f: F) {
+        self.sequences.retain(f);
+    }
+
+    /// Remove and return the sequence at `index`, shifting all sequences after it to the front.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> OwnedFastaSequence {
+        self.sequences.remove(index)
+    }
+
+    /// Returns an iterator over mutable references to the sequences,
+    /// for example to edit descriptions or sequences in place.
+    pub fn records_mut(&mut self) -> impl Iterator<Item = &mut OwnedFastaSequence> {
+        self.sequences.iter_mut()
+    }
+}
+
+impl From<&Fasta<'_>> for OwnedFasta {
+    fn from(fasta: &Fasta<'_>) -> Self {
+        fasta
+            .sequences
+            .iter()
+            .map(OwnedFastaSequence::from)
+            .collect()
+    }
+}
+
+impl FromIterator<OwnedFastaSequence> for OwnedFasta {
+    fn from_iter<T: IntoIterator<Item = OwnedFastaSequence>>(iter: T) -> Self {
+        Self {
+            sequences: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl OwnedFastaSequence {
+    /// Create a new sequence from a description (without the leading `>`) and the sequence
+    /// characters.
+    /// The sequence is stored as is, so it should not contain newlines.
+    pub fn new(description: impl Into<Vec<u8>>, sequence: impl Into<Vec<u8>>) -> Self {
+        Self {
+            description: description.into(),
+            sequence: sequence.into(),
+        }
+    }
+
+    /// Returns the sequence description without the leading `>` character.
+    pub fn description(&self) -> &[u8] {
+        &self.description
+    }
+
+    /// Returns the sequence characters without newlines.
+    pub fn sequence(&self) -> &[u8] {
+        &self.sequence
+    }
+
+    /// Returns a mutable reference to the description, for example to rename the sequence.
+    pub fn description_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.description
+    }
+
+    /// Returns a mutable reference to the sequence characters.
+    /// The sequence should not contain newlines.
+    pub fn sequence_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.sequence
+    }
+
+    /// Returns the number of sequence characters.
+    pub fn len(&self) -> usize {
+        self.sequence.len()
+    }
+
+    /// Returns true if the sequence contains no characters.
+    pub fn is_empty(&self) -> bool {
+        self.sequence.is_empty()
+    }
+}
+
+impl From<&FastaSequence<'_>> for OwnedFastaSequence {
+    /// Copy the description and the sequence without newlines (see [`copy_sequential`]).
+    ///
+    /// [`copy_sequential`]: FastaSequence::copy_sequential
+    fn from(sequence: &FastaSequence<'_>) -> Self {
+        Self {
+            description: sequence.description.to_vec(),
+            sequence: sequence.copy_sequential().into_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
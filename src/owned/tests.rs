@@ -0,0 +1,61 @@
+use crate::{parse_fasta, OwnedFasta, OwnedFastaSequence};
+
+#[test]
+fn test_owned_from_fasta() {
+    let fasta = parse_fasta(b">a desc\nAC\nGT\n\n>b\nTT").expect("Failed to parse FASTA");
+    let owned = OwnedFasta::from(&fasta);
+
+    assert_eq!(
+        owned.sequences(),
+        &[
+            OwnedFastaSequence::new(b"a desc".to_vec(), b"ACGT".to_vec()),
+            OwnedFastaSequence::new(b"b".to_vec(), b"TT".to_vec()),
+        ]
+    );
+}
+
+#[test]
+fn test_owned_retain() {
+    let fasta = parse_fasta(b">a\nACGT\n>b\nTT\n>c\nGGG\nGG").expect("Failed to parse FASTA");
+    let mut owned = OwnedFasta::from(&fasta);
+
+    owned.retain(|seq| seq.len() > 2);
+    let descriptions = owned
+        .sequences()
+        .iter()
+        .map(OwnedFastaSequence::description)
+        .collect::<Vec<_>>();
+    assert_eq!(descriptions, vec![&b"a"[..], b"c"]);
+}
+
+#[test]
+fn test_owned_remove() {
+    let fasta = parse_fasta(b">a\nACGT\n>b\nTT\n>c\nGGG").expect("Failed to parse FASTA");
+    let mut owned = OwnedFasta::from(&fasta);
+
+    let removed = owned.remove(1);
+    assert_eq!(
+        removed,
+        OwnedFastaSequence::new(b"b".to_vec(), b"TT".to_vec())
+    );
+    assert_eq!(owned.len(), 2);
+    assert_eq!(owned.sequences()[1].description(), b"c");
+}
+
+#[test]
+fn test_owned_records_mut() {
+    let fasta = parse_fasta(b">a\nacgt\n>b\nTt").expect("Failed to parse FASTA");
+    let mut owned = OwnedFasta::from(&fasta);
+
+    for record in owned.records_mut() {
+        record.description_mut().extend_from_slice(b"_new");
+        record.sequence_mut().make_ascii_uppercase();
+    }
+    assert_eq!(
+        owned.sequences(),
+        &[
+            OwnedFastaSequence::new(b"a_new".to_vec(), b"ACGT".to_vec()),
+            OwnedFastaSequence::new(b"b_new".to_vec(), b"TT".to_vec()),
+        ]
+    );
+}
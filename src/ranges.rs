@@ -0,0 +1,267 @@
+use crate::{expect, Newline, ParseError, ParseOptions, RecordStart};
+use memchr::memmem::Finder;
+use memchr::{memchr, memchr_iter};
+use std::iter::FusedIterator;
+use std::ops::Range;
+
+/// The byte ranges of the description and the sequence of one record.
+type RecordRange = (Range<usize>, Range<usize>);
+
+/// The byte range of a full record, from the start of its description line to the end of its
+/// sequence, and the ranges of its description and sequence.
+pub(crate) type SpannedRecord = (Range<usize>, RecordRange);
+
+/// Lazily identify the records of a FASTA or Multi FASTA file and yield their byte ranges.
+/// Each item is a pair of the description range (without the leading `>` and the trailing newline)
+/// and the raw sequence range (including newlines) in `data`.
+///
+/// This is the lowest-level parsing primitive of the crate:
+/// It performs the same scan as [`parse_fasta`], but doesn't allocate,
+/// so it can be used to build custom data structures over the input.
+///
+/// ```rust
+/// # use fire_fasta::record_ranges;
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let data = b">Sample1\nACGT\n>Sample2\nAC";
+/// let ranges = record_ranges(data).collect::<Result<Vec<_>, _>>()?;
+///
+/// assert_eq!(ranges, vec![(1..8, 9..14), (15..22, 23..25)]);
+/// assert_eq!(&data[ranges[1].0.clone()], b"Sample2");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Yields the same errors as [`parse_fasta`].
+/// After an error is yielded, the iterator ends.
+///
+/// [`parse_fasta`]: crate::parse_fasta
+pub fn record_ranges(data: &[u8]) -> RecordRanges<'_> {
+    RecordRanges::with_options(data, ParseOptions::default())
+}
+
+/// An iterator over the byte ranges of the records in a FASTA file,
+/// created by [`record_ranges`].
+#[derive(Clone, Debug)]
+pub struct RecordRanges<'a> {
+    data: &'a [u8],
+    cursor: usize,
+    /// The searcher for `\n>`, if records only start at the beginning of a line.
+    line_start: Option<Finder<'static>>,
+    /// Whether the next record may be a sequence without a description.
+    leading_sequence: bool,
+    max_header_len: Option<usize>,
+    trim_descriptions: bool,
+    /// Whether records are separated by blank lines instead of starting with `>`.
+    blank_line_separator: bool,
+    newline: Newline,
+}
+
+impl<'a> RecordRanges<'a> {
+    /// Create an iterator over the records of `data` that respects the scanning-related options.
+    pub(crate) fn with_options(data: &'a [u8], options: ParseOptions) -> Self {
+        let cursor = if options.skip_leading_blank_lines {
+            data.iter()
+                .position(|c| !c.is_ascii_whitespace())
+                .unwrap_or(data.len())
+        } else {
+            0
+        };
+
+        Self {
+            data,
+            cursor,
+            line_start: (options.record_start == RecordStart::LineStart)
+                .then(|| Finder::new(b"\n>")),
+            leading_sequence: options.assume_leading_sequence,
+            max_header_len: options.max_header_len,
+            trim_descriptions: options.trim_descriptions,
+            blank_line_separator: options.blank_line_separator,
+            newline: options.newline,
+        }
+    }
+
+    /// Find the description line that starts at `cursor`, for a record starting at `record_start`.
+    /// Returns the description range, narrowed if descriptions are trimmed,
+    /// and the end of the line, i.e. the offset of its newline or the end of the input.
+    fn header(
+        &self,
+        record_start: usize,
+        cursor: usize,
+    ) -> Result<(Range<usize>, usize), ParseError> {
+        let data = self.data;
+        let header_end = match self.max_header_len {
+            Some(limit) => {
+                let window = &data[cursor..];
+                let window = &window[..window.len().min(limit.saturating_add(1))];
+                match memchr(b'\n', window) {
+                    Some(header_end) => header_end,
+                    None if window.len() > limit => {
+                        return Err(ParseError::HeaderTooLong {
+                            offset: record_start,
+                        });
+                    }
+                    None => window.len(),
+                }
+            }
+            None => memchr(b'\n', &data[cursor..]).unwrap_or(data.len() - cursor),
+        };
+        let line_end = cursor + header_end;
+
+        let mut header = cursor..line_end;
+        if self.trim_descriptions {
+            while header.start < header.end && data[header.start].is_ascii_whitespace() {
+                header.start += 1;
+            }
+            while header.start < header.end && data[header.end - 1].is_ascii_whitespace() {
+                header.end -= 1;
+            }
+        }
+
+        Ok((header, line_end))
+    }
+
+    /// Returns an iterator over the records like [`next`], paired with the byte range of the
+    /// full record.
+    ///
+    /// [`next`]: RecordRanges::next
+    pub(crate) fn spanned(
+        mut self,
+    ) -> impl Iterator<Item = Result<SpannedRecord, ParseError>> + 'a {
+        std::iter::from_fn(move || self.next_spanned())
+    }
+
+    /// Yield the next record like [`next`], paired with the byte range of the full record.
+    ///
+    /// [`next`]: RecordRanges::next
+    fn next_spanned(&mut self) -> Option<Result<SpannedRecord, ParseError>> {
+        let data = self.data;
+        if self.cursor >= data.len() {
+            return None;
+        }
+
+        let mut cursor = self.cursor;
+        // any error ends the iteration
+        self.cursor = data.len();
+
+        if self.blank_line_separator {
+            return self.next_block(cursor);
+        }
+
+        if std::mem::take(&mut self.leading_sequence) && data[cursor] != b'>' {
+            let sequence_end = match &self.line_start {
+                Some(finder) => finder.find(&data[cursor..]).map(|pivot| pivot + 1),
+                None => memchr(b'>', &data[cursor..]),
+            }
+            .unwrap_or(data.len() - cursor);
+            self.cursor = cursor + sequence_end;
+
+            let sequence = cursor..cursor + sequence_end;
+            return Some(Ok((sequence.clone(), (cursor..cursor, sequence))));
+        }
+
+        let record_start = cursor;
+        if !expect(data, b'>', &mut cursor) {
+            return Some(Err(ParseError::InvalidDescription {
+                invalid: data[cursor],
+            }));
+        }
+
+        let (header, header_end) = match self.header(record_start, cursor) {
+            Ok(header) => header,
+            Err(err) => return Some(Err(err)),
+        };
+        cursor = header_end + 1;
+
+        if cursor >= data.len() {
+            return Some(Err(ParseError::EmptySequence));
+        }
+
+        let sequence_end = match &self.line_start {
+            // start the search at the newline of the header, so an empty sequence is recognized
+            Some(finder) => finder.find(&data[cursor - 1..]),
+            None => memchr(b'>', &data[cursor..]),
+        }
+        .unwrap_or(data.len() - cursor);
+        // may contain trailing white space
+        let sequence = cursor..cursor + sequence_end;
+        self.cursor = cursor + sequence_end;
+
+        Some(Ok((record_start..sequence.end, (header, sequence))))
+    }
+
+    /// Yield the next record of input in which records are separated by blank lines
+    /// (see [`ParseOptions::blank_line_separator`]).
+    /// The record starts at the first non-blank line at or after `cursor`.
+    fn next_block(&mut self, cursor: usize) -> Option<Result<SpannedRecord, ParseError>> {
+        let data = self.data;
+        let cursor = self.skip_blank_lines(cursor);
+        if cursor >= data.len() {
+            return None;
+        }
+
+        let (header, header_end) = match self.header(cursor, cursor) {
+            Ok(header) => header,
+            Err(err) => return Some(Err(err)),
+        };
+        let sequence_start = (header_end + 1).min(data.len());
+
+        // the sequence includes the blank lines that separate it from the next record
+        let blank_line = self.find_blank_line(sequence_start);
+        let sequence_end = self.skip_blank_lines(blank_line);
+        self.cursor = sequence_end;
+
+        Some(Ok((
+            cursor..sequence_end,
+            (header, sequence_start..sequence_end),
+        )))
+    }
+
+    /// Returns the start of the first blank line at or after the line starting at `cursor`,
+    /// or the end of the input.
+    fn find_blank_line(&self, cursor: usize) -> usize {
+        let data = self.data;
+        let mut line_start = cursor;
+        for line_end in memchr_iter(b'\n', &data[cursor..]).map(|pivot| cursor + pivot) {
+            if self.is_blank(&data[line_start..line_end]) {
+                return line_start;
+            }
+            line_start = line_end + 1;
+        }
+        data.len()
+    }
+
+    /// Returns the start of the first non-blank line at or after the line starting at `cursor`,
+    /// or the end of the input.
+    fn skip_blank_lines(&self, mut cursor: usize) -> usize {
+        let data = self.data;
+        while let Some(line_end) = memchr(b'\n', &data[cursor..]).map(|pivot| cursor + pivot) {
+            if !self.is_blank(&data[cursor..line_end]) {
+                break;
+            }
+            cursor = line_end + 1;
+        }
+        cursor
+    }
+
+    /// Returns true if a line (without its `LF`) is blank.
+    /// In [`Newline::Auto`] mode, a line consisting of a single `CR` is blank as well.
+    fn is_blank(&self, line: &[u8]) -> bool {
+        line.is_empty() || (self.newline == Newline::Auto && line == b"\r")
+    }
+}
+
+impl Iterator for RecordRanges<'_> {
+    type Item = Result<RecordRange, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_spanned()
+            .map(|record| record.map(|(_, record)| record))
+    }
+}
+
+impl FusedIterator for RecordRanges<'_> {}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,36 @@
+use crate::{record_ranges, ParseError};
+
+#[test]
+fn test_record_ranges() {
+    let data = b">a\nAC\nGT\n\n>\nT>c d\nG";
+    let ranges = record_ranges(data)
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Failed to parse FASTA");
+
+    assert_eq!(
+        ranges,
+        vec![(1..2, 3..10), (11..11, 12..13), (14..17, 18..19)]
+    );
+    assert_eq!(&data[ranges[2].0.clone()], b"c d");
+    assert_eq!(&data[ranges[0].1.clone()], b"AC\nGT\n\n");
+
+    assert_eq!(record_ranges(b"").count(), 0);
+}
+
+#[test]
+fn test_record_ranges_errors() {
+    let mut ranges = record_ranges(b"ACGT");
+    assert!(matches!(
+        ranges.next(),
+        Some(Err(ParseError::InvalidDescription { invalid: b'A' }))
+    ));
+    assert!(ranges.next().is_none());
+
+    let mut ranges = record_ranges(b">a\nAC\n>b\n");
+    assert!(matches!(ranges.next(), Some(Ok(_))));
+    assert!(matches!(
+        ranges.next(),
+        Some(Err(ParseError::EmptySequence))
+    ));
+    assert!(ranges.next().is_none());
+}
@@ -0,0 +1,161 @@
+use crate::{FastaSequence, Newline, ParseError};
+use std::io;
+use std::io::{BufRead, BufReader, Read};
+use std::ops::Range;
+
+/// A streaming FASTA parser that reads one record at a time from a [`Read`] implementation.
+/// The reader owns a buffer holding the current record, and returns [`FastaSequences`] borrowing
+/// from that buffer, so the memory consumption is bounded by the largest record in the input.
+///
+/// The records are parsed with the same rules as [`parse_fasta`].
+///
+/// ```rust
+/// # use fire_fasta::FastaReader;
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let data = std::io::Cursor::new(b">Sample1\nACGT\nCA\n>Sample2\nACGTCC");
+/// let mut reader = FastaReader::new(data);
+///
+/// let first = reader.next_record()?.unwrap();
+/// assert_eq!(first.description, b"Sample1");
+/// assert_eq!(first.copy_sequential().as_ref(), b"ACGTCA");
+///
+/// let second = reader.next_record()?.unwrap();
+/// assert_eq!(second.description, b"Sample2");
+/// assert!(reader.next_record()?.is_none());
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`FastaSequences`]: FastaSequence
+/// [`parse_fasta`]: crate::parse_fasta
+pub struct FastaReader<R: Read> {
+    reader: BufReader<R>,
+    buffer: Vec<u8>,
+    /// Whether the first record start has been consumed already
+    started: bool,
+    /// Whether the end of input (or an error) was reached
+    finished: bool,
+    /// The number of bytes consumed from the input so far
+    offset: usize,
+}
+
+impl<R: Read> FastaReader<R> {
+    /// Create a new streaming parser reading from `reader`.
+    /// The reader is buffered internally, so there is no need to wrap it into a [`BufReader`].
+    ///
+    /// Since `&mut dyn Read` implements [`Read`], the parser can also read from type-erased
+    /// readers (see also [`new_boxed`]).
+    ///
+    /// [`new_boxed`]: FastaReader::new_boxed
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+            buffer: Vec::new(),
+            started: false,
+            finished: false,
+            offset: 0,
+        }
+    }
+
+    /// Parse the next record from the input.
+    /// The returned sequence borrows from the internal buffer of the reader,
+    /// which is reused for the next record.
+    ///
+    /// # Errors
+    /// Any I/O error of the underlying reader is returned as is.
+    /// Parsing errors (see [`parse_fasta`]) are returned as an [`io::Error`] of kind
+    /// [`InvalidData`] wrapping the respective [`ParseError`].
+    /// After an error, the reader doesn't return any further records.
+    ///
+    /// # Returns
+    /// The next record, or `None` if the input is exhausted.
+    ///
+    /// [`parse_fasta`]: crate::parse_fasta
+    /// [`InvalidData`]: io::ErrorKind::InvalidData
+    pub fn next_record(&mut self) -> io::Result<Option<FastaSequence<'_>>> {
+        if self.finished {
+            return Ok(None);
+        }
+
+        match self.read_record() {
+            Ok(Some((header_end, span))) => Ok(Some(FastaSequence {
+                description: &self.buffer[..header_end],
+                sequence: &self.buffer[header_end + 1..],
+                newline: Newline::Lf,
+                strip_spaces: false,
+                span,
+            })),
+            Ok(None) => {
+                self.finished = true;
+                Ok(None)
+            }
+            Err(e) => {
+                self.finished = true;
+                Err(e)
+            }
+        }
+    }
+
+    /// Read the next record into the buffer and return the length of its description and the
+    /// byte range of the record in the input, or `None` if the input is exhausted.
+    fn read_record(&mut self) -> io::Result<Option<(usize, Range<usize>)>> {
+        self.buffer.clear();
+
+        if !self.started {
+            self.started = true;
+            let available = self.reader.fill_buf()?;
+            match available.first() {
+                None => return Ok(None),
+                Some(b'>') => {
+                    self.reader.consume(1);
+                    self.offset += 1;
+                }
+                Some(&invalid) => {
+                    return Err(invalid_data(ParseError::InvalidDescription { invalid }))
+                }
+            }
+        }
+
+        // the `>` of the record has been consumed already
+        let record_start = self.offset - 1;
+        self.offset += self.reader.read_until(b'\n', &mut self.buffer)?;
+        if self.buffer.last() == Some(&b'\n') {
+            self.buffer.pop();
+        }
+        let header_end = self.buffer.len();
+        self.buffer.push(b'\n');
+
+        if self.reader.fill_buf()?.is_empty() {
+            return Err(invalid_data(ParseError::EmptySequence));
+        }
+
+        self.offset += self.reader.read_until(b'>', &mut self.buffer)?;
+        let record_end = if self.buffer.last() == Some(&b'>') {
+            self.buffer.pop();
+            self.offset - 1
+        } else {
+            self.finished = true;
+            self.offset
+        };
+
+        Ok(Some((header_end, record_start..record_end)))
+    }
+}
+
+impl FastaReader<Box<dyn Read>> {
+    /// Create a new streaming parser reading from a type-erased reader.
+    /// This avoids monomorphizing the parser for every reader type, for example in plugin
+    /// architectures that pass readers around as trait objects.
+    pub fn new_boxed(reader: Box<dyn Read>) -> Self {
+        Self::new(reader)
+    }
+}
+
+/// Wrap a parsing error into an [`io::Error`]
+fn invalid_data(error: ParseError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+#[cfg(test)]
+mod tests;
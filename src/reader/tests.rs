@@ -0,0 +1,87 @@
+use crate::{parse_fasta, FastaReader, ParseError};
+use std::io::{Cursor, ErrorKind, Read};
+
+/// Collect all records of the reader as (description, sequence) pairs
+fn collect_records<R: Read>(reader: &mut FastaReader<R>) -> Vec<(Vec<u8>, Vec<u8>)> {
+    let mut records = Vec::new();
+    while let Some(record) = reader.next_record().expect("Failed to read FASTA") {
+        records.push((
+            record.description.to_vec(),
+            record.copy_sequential().into_vec(),
+        ));
+    }
+    records
+}
+
+#[test]
+fn test_record_spans() {
+    let data = ">first\nACGT\nAC\n\n>second\nGGG\n>\nT";
+    let expected = parse_fasta(data.as_bytes()).expect("Failed to parse FASTA");
+    let mut reader = FastaReader::new(Cursor::new(data));
+
+    let mut spans = Vec::new();
+    while let Some(record) = reader.next_record().expect("Failed to read FASTA") {
+        spans.push(record.span.clone());
+    }
+    assert_eq!(spans, vec![0..16, 16..28, 28..31]);
+    let expected = expected.records_with_spans().map(|(_, span)| span);
+    assert!(spans.into_iter().eq(expected));
+}
+
+#[test]
+fn test_read_multi_sequence() {
+    let data = ">first\nACGT\nAC\n\n>second\nGGG\n>\nT";
+    let mut reader = FastaReader::new(Cursor::new(data));
+
+    assert_eq!(
+        collect_records(&mut reader),
+        vec![
+            (b"first".to_vec(), b"ACGTAC".to_vec()),
+            (b"second".to_vec(), b"GGG".to_vec()),
+            (b"".to_vec(), b"T".to_vec()),
+        ]
+    );
+}
+
+#[test]
+fn test_read_boxed() {
+    let boxed: Box<dyn Read> = Box::new(Cursor::new(b">a\nAC\nGT\n>b\nTT\n".to_vec()));
+    let mut reader = FastaReader::new_boxed(boxed);
+
+    assert_eq!(
+        collect_records(&mut reader),
+        vec![
+            (b"a".to_vec(), b"ACGT".to_vec()),
+            (b"b".to_vec(), b"TT".to_vec()),
+        ]
+    );
+
+    let mut cursor = Cursor::new(b">c\nA");
+    let mut reader = FastaReader::new(&mut cursor as &mut dyn Read);
+    assert_eq!(
+        collect_records(&mut reader),
+        vec![(b"c".to_vec(), b"A".to_vec())]
+    );
+}
+
+#[test]
+fn test_read_errors() {
+    let mut reader = FastaReader::new(Cursor::new(""));
+    assert!(reader.next_record().unwrap().is_none());
+
+    let mut reader = FastaReader::new(Cursor::new("ACGT"));
+    let error = reader.next_record().unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert!(matches!(
+        error.into_inner().unwrap().downcast_ref::<ParseError>(),
+        Some(ParseError::InvalidDescription { invalid: b'A' })
+    ));
+    assert!(reader.next_record().unwrap().is_none());
+
+    let mut reader = FastaReader::new(Cursor::new(">a\nA\n>b\n"));
+    assert!(reader.next_record().unwrap().is_some());
+    assert_eq!(
+        reader.next_record().unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+}
@@ -0,0 +1,317 @@
+use crate::{Fasta, FastaSequence};
+use std::collections::VecDeque;
+use std::hash::{BuildHasher, BuildHasherDefault, Hasher};
+
+/// A streaming 64-bit FNV-1a hasher.
+/// It is the built-in hash function of the content-hashing methods of [`FastaSequence`].
+/// It is fast for short inputs like k-mers, but not resistant against collision attacks.
+///
+/// The hash of a byte string doesn't depend on how it is split into [`write`] calls.
+///
+/// [`write`]: Hasher::write
+#[derive(Clone, Copy, Debug)]
+pub struct FnvHasher(u64);
+
+/// A [`BuildHasher`] that creates [`FnvHasher`]s.
+pub type FnvBuildHasher = BuildHasherDefault<FnvHasher>;
+
+impl Default for FnvHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for FnvHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0 = bytes.iter().fold(self.0, |hash, &b| {
+            (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+        });
+    }
+}
+
+impl<'a> FastaSequence<'a> {
+    /// Returns a checksum of the residues of the sequence, computed with [`FnvHasher`].
+    /// Newlines are skipped, so the checksum doesn't depend on the line wrapping of the sequence,
+    /// and the description is not part of the checksum.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">a\nACGT\nCA\n>b\nACG\nTCA")?;
+    /// assert_eq!(fasta.sequences[0].checksum(), fasta.sequences[1].checksum());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn checksum(&self) -> u64 {
+        self.checksum_with(FnvHasher::default())
+    }
+
+    /// Returns a checksum of the residues of the sequence, computed with the given hasher.
+    /// The residues are fed to the hasher line by line with [`Hasher::write`],
+    /// so the checksum is only independent of the line wrapping if the hasher is independent of
+    /// how its input is split into writes, like [`FnvHasher`] and the standard library hashers.
+    pub fn checksum_with<H: Hasher>(&self, mut hasher: H) -> u64 {
+        for segment in self.segments() {
+            hasher.write(segment);
+        }
+        hasher.finish()
+    }
+
+    /// Returns an iterator over all k-mers (substrings of length `k`) of the sequence,
+    /// skipping newlines, so k-mers may span line breaks.
+    /// The residues are copied into a buffer once, and each k-mer is then copied from it.
+    /// Sequences shorter than `k` residues, and `k == 0`, yield nothing.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">seq\nAC\nGT")?;
+    /// let kmers = fasta.sequences[0].kmers(3).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(kmers, vec![b"ACG".to_vec().into(), b"CGT".to_vec().into()]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn kmers(&self, k: usize) -> impl Iterator<Item = Box<[u8]>> {
+        let residues = self.copy_sequential();
+        let kmer_count = if k == 0 {
+            0
+        } else {
+            (residues.len() + 1).saturating_sub(k)
+        };
+        (0..kmer_count).map(move |i| Box::from(&residues[i..i + k]))
+    }
+
+    /// Returns an iterator over the minimizers of the sequence.
+    /// For each window of `w` consecutive k-mers (of length `k`), the iterator yields the
+    /// position and hash of the k-mer with the smallest hash in the window.
+    /// If multiple k-mers in a window share the smallest hash, the leftmost one is chosen.
+    /// Positions are residue offsets, skipping newlines, so k-mers may span line breaks.
+    ///
+    /// K-mers are hashed with the canonical ntHash of [`rolling_hashes`], which computes each hash
+    /// from the previous one in constant time.
+    /// See [`minimizers_with`] to use another hash function.
+    /// K-mers that contain any character other than `A`, `C`, `G`, and `T` have no hash and are
+    /// skipped, so a window consists of `w` consecutive hashed k-mers.
+    /// Sequences shorter than `k + w - 1` residues, and `k == 0` or `w == 0`, yield nothing.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">seq\nACGTAC\nGTTA")?;
+    /// let minimizers = fasta.sequences[0].minimizers(3, 4).collect::<Vec<_>>();
+    ///
+    /// // 8 3-mers form 5 windows of 4 3-mers
+    /// assert_eq!(minimizers.len(), 5);
+    /// assert!(minimizers.iter().all(|&(pos, _)| pos < 8));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`rolling_hashes`]: FastaSequence::rolling_hashes
+    /// [`minimizers_with`]: FastaSequence::minimizers_with
+    pub fn minimizers(&self, k: usize, w: usize) -> impl Iterator<Item = (usize, u64)> + '_ {
+        window_minima(self.rolling_hash_offsets(k), w)
+    }
+
+    /// Returns an iterator over the minimizers of the sequence like [`minimizers`],
+    /// but hashes each k-mer with a hasher created by `build_hasher`, for example [`FnvBuildHasher`].
+    /// Each k-mer is written to a fresh hasher with a single [`Hasher::write`] call,
+    /// and no k-mers are skipped.
+    ///
+    /// [`minimizers`]: FastaSequence::minimizers
+    pub fn minimizers_with<B: BuildHasher>(
+        &self,
+        k: usize,
+        w: usize,
+        build_hasher: B,
+    ) -> impl Iterator<Item = (usize, u64)> {
+        let residues = self.copy_sequential();
+        let kmer_count = if k == 0 || w == 0 {
+            0
+        } else {
+            (residues.len() + 1).saturating_sub(k)
+        };
+
+        let kmers = (0..kmer_count).map(move |i| {
+            let mut hasher = build_hasher.build_hasher();
+            hasher.write(&residues[i..i + k]);
+            (i, hasher.finish())
+        });
+        window_minima(kmers, w)
+    }
+}
+
+impl<'a> FastaSequence<'a> {
+    /// Returns an iterator over the canonical [ntHash](https://doi.org/10.1093/bioinformatics/btw397)
+    /// values of the k-mers of the sequence, skipping newlines.
+    /// The hash of each k-mer is computed from the hash of the previous k-mer in constant time,
+    /// and the canonical hash is the minimum of the forward hash and the hash of the reverse
+    /// complement, so a k-mer and its reverse complement have the same hash.
+    ///
+    /// Bases are hashed case-insensitively.
+    /// K-mers that contain any character other than `A`, `C`, `G`, and `T` are skipped:
+    /// Such a character resets the rolling state, and the next hash is yielded for the first k-mer
+    /// after it.
+    /// For `k == 0`, the iterator yields nothing.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">fwd\nAACG\nTN\n>rev\nACGTT")?;
+    /// let forward = fasta.sequences[0].rolling_hashes(3).collect::<Vec<_>>();
+    /// let reverse = fasta.sequences[1].rolling_hashes(3).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(forward.len(), 3);
+    /// assert!(forward.iter().eq(reverse.iter().rev()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rolling_hashes(&self, k: usize) -> impl Iterator<Item = u64> + '_ {
+        self.rolling_hash_offsets(k).map(|(_, hash)| hash)
+    }
+
+    /// Returns an iterator over the rolling hashes like [`rolling_hashes`],
+    /// paired with the residue offset of the first base of each k-mer.
+    ///
+    /// [`rolling_hashes`]: FastaSequence::rolling_hashes
+    fn rolling_hash_offsets(&self, k: usize) -> impl Iterator<Item = (usize, u64)> + '_ {
+        let mut residues = self.iter();
+        // trails `residues` by k characters and yields the base leaving the window
+        let mut trailing = self.iter();
+        let mut seen = 0;
+        // the number of consecutive bases at the end of the window
+        let mut run = 0;
+        let mut forward = 0u64;
+        let mut reverse = 0u64;
+
+        std::iter::from_fn(move || {
+            if k == 0 {
+                return None;
+            }
+
+            loop {
+                let base = *residues.next()?;
+                let leaving = if seen >= k {
+                    trailing.next().copied()
+                } else {
+                    None
+                };
+                seen += 1;
+
+                let Some((base_hash, complement_hash)) = nt_seeds(base) else {
+                    run = 0;
+                    forward = 0;
+                    reverse = 0;
+                    continue;
+                };
+
+                if run < k {
+                    forward = forward.rotate_left(1) ^ base_hash;
+                    reverse ^= complement_hash.rotate_left((run % 64) as u32);
+                    run += 1;
+                    if run < k {
+                        continue;
+                    }
+                } else {
+                    // a full window only contains bases
+                    let (leaving_hash, leaving_complement_hash) =
+                        leaving.and_then(nt_seeds).unwrap();
+                    forward = forward.rotate_left(1)
+                        ^ leaving_hash.rotate_left((k % 64) as u32)
+                        ^ base_hash;
+                    reverse = reverse.rotate_right(1)
+                        ^ leaving_complement_hash.rotate_right(1)
+                        ^ complement_hash.rotate_left(((k - 1) % 64) as u32);
+                }
+
+                return Some((seen - k, forward.min(reverse)));
+            }
+        })
+    }
+}
+
+impl<'a> Fasta<'a> {
+    /// Returns an iterator over the k-mers of all sequences (see [`FastaSequence::kmers`]),
+    /// for example to count the k-mers of a whole database.
+    /// The k-mers of each sequence are yielded in order, and k-mers never span two records.
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">a\nACG\n>b\nTT\n>c\nCAA")?;
+    /// let kmers = fasta.all_kmers(2).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(kmers.len(), 5);
+    /// assert!(!kmers.contains(&b"GT".to_vec().into()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn all_kmers(&self, k: usize) -> impl Iterator<Item = Box<[u8]>> + '_ {
+        self.sequences.iter().flat_map(move |seq| seq.kmers(k))
+    }
+}
+
+/// Returns an iterator over the minimum of each window of `w` consecutive hashed k-mers,
+/// given as pairs of their position and hash.
+/// The leftmost k-mer is chosen if multiple k-mers share the smallest hash,
+/// and fewer than `w` k-mers, or `w == 0`, yield nothing.
+fn window_minima(
+    mut kmers: impl Iterator<Item = (usize, u64)>,
+    w: usize,
+) -> impl Iterator<Item = (usize, u64)> {
+    // candidates for the minimum of the current window, with increasing hashes,
+    // each with its index in `kmers`
+    let mut window = VecDeque::<(usize, usize, u64)>::with_capacity(w);
+    let mut next = 0;
+    std::iter::from_fn(move || {
+        if w == 0 {
+            return None;
+        }
+
+        for (pos, hash) in kmers.by_ref() {
+            while window.back().is_some_and(|&(_, _, back)| back > hash) {
+                window.pop_back();
+            }
+            window.push_back((next, pos, hash));
+            if window[0].0 + w <= next {
+                window.pop_front();
+            }
+
+            next += 1;
+            if next >= w {
+                return window.front().map(|&(_, pos, hash)| (pos, hash));
+            }
+        }
+        None
+    })
+}
+
+/// Returns the ntHash seeds of a base and of its complement, or `None` if it isn't a base.
+#[inline]
+fn nt_seeds(base: u8) -> Option<(u64, u64)> {
+    const A: u64 = 0x3c8b_fbb3_95c6_0474;
+    const C: u64 = 0x3193_c185_62a0_2b4c;
+    const G: u64 = 0x2032_3ed0_8257_2324;
+    const T: u64 = 0x2955_49f5_4be2_4456;
+
+    match base.to_ascii_uppercase() {
+        b'A' => Some((A, T)),
+        b'C' => Some((C, G)),
+        b'G' => Some((G, C)),
+        b'T' => Some((T, A)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,179 @@
+use crate::{parse_fasta, FnvBuildHasher, FnvHasher};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+/// Hash `bytes` with a single write into a fresh hasher.
+fn write_once<B: BuildHasher>(build_hasher: &B, bytes: &[u8]) -> u64 {
+    let mut hasher = build_hasher.build_hasher();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+/// Compute the minimum of each window of `w` consecutive k-mers directly from its definition.
+fn expected_minimizers(kmers: &[(usize, u64)], w: usize) -> Vec<(usize, u64)> {
+    kmers
+        .windows(w)
+        .map(|window| *window.iter().min_by_key(|&&(_, hash)| hash).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_minimizers() {
+    let fasta = parse_fasta(b">a\nACGTTG\nCATGA\n\nCCGTA\n>b\nACGTNAC\nGTTNNACGGA")
+        .expect("Failed to parse FASTA");
+
+    for seq in &fasta.sequences {
+        let residues = seq.copy_sequential();
+        for (k, w) in [(1, 1), (3, 4), (5, 2), (2, 10)] {
+            // k-mers with other characters than bases have no ntHash
+            let kmers = residues
+                .windows(k)
+                .enumerate()
+                .filter(|(_, kmer)| kmer.iter().all(|&c| super::nt_seeds(c).is_some()))
+                .map(|(pos, kmer)| (pos, nt_hash(kmer)))
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                seq.minimizers(k, w).collect::<Vec<_>>(),
+                expected_minimizers(&kmers, w),
+                "k = {k}, w = {w}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_minimizers_short_sequence() {
+    let fasta = parse_fasta(b">a\nACG\nT").expect("Failed to parse FASTA");
+    let seq = &fasta.sequences[0];
+
+    assert_eq!(seq.minimizers(2, 3).count(), 1);
+    assert_eq!(seq.minimizers(2, 4).count(), 0);
+    assert_eq!(seq.minimizers(5, 1).count(), 0);
+    assert_eq!(seq.minimizers(0, 1).count(), 0);
+    assert_eq!(seq.minimizers(1, 0).count(), 0);
+}
+
+#[test]
+fn test_fnv_hasher() {
+    let mut hasher = FnvHasher::default();
+    assert_eq!(hasher.finish(), 0xcbf2_9ce4_8422_2325);
+    hasher.write(b"a");
+    assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+}
+
+#[test]
+fn test_checksum() {
+    let fasta = parse_fasta(b">a\nAC\nGT\n>b x\nACGT\n\n>c\nACGA").expect("Failed to parse FASTA");
+
+    assert_eq!(fasta.sequences[0].checksum(), fasta.sequences[1].checksum());
+    assert_ne!(fasta.sequences[0].checksum(), fasta.sequences[2].checksum());
+
+    let mut hasher = FnvHasher::default();
+    hasher.write(b"ACGT");
+    assert_eq!(fasta.sequences[0].checksum(), hasher.finish());
+
+    let state = RandomState::new();
+    assert_eq!(
+        fasta.sequences[0].checksum_with(state.build_hasher()),
+        fasta.sequences[1].checksum_with(state.build_hasher())
+    );
+}
+
+#[test]
+fn test_minimizers_with() {
+    let fasta = parse_fasta(b">a\nACGTTG\nCATGA").expect("Failed to parse FASTA");
+    let seq = &fasta.sequences[0];
+
+    let residues = seq.copy_sequential();
+    for (k, w) in [(1, 1), (3, 4), (5, 2)] {
+        let kmers = residues
+            .windows(k)
+            .map(|kmer| write_once(&FnvBuildHasher::default(), kmer))
+            .enumerate()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            seq.minimizers_with(k, w, FnvBuildHasher::default())
+                .collect::<Vec<_>>(),
+            expected_minimizers(&kmers, w)
+        );
+    }
+
+    let state = RandomState::new();
+    let minimizers = seq.minimizers_with(3, 2, state.clone()).collect::<Vec<_>>();
+    assert_eq!(minimizers.len(), 8);
+    assert!(minimizers
+        .iter()
+        .all(|&(pos, hash)| write_once(&state, &residues[pos..pos + 3]) == hash));
+}
+
+/// Compute the canonical ntHash of a k-mer directly from its definition.
+fn nt_hash(kmer: &[u8]) -> u64 {
+    let k = kmer.len();
+    let mut forward = 0u64;
+    let mut reverse = 0u64;
+    for (i, &base) in kmer.iter().enumerate() {
+        let (base_hash, complement_hash) = super::nt_seeds(base).unwrap();
+        forward ^= base_hash.rotate_left(((k - 1 - i) % 64) as u32);
+        reverse ^= complement_hash.rotate_left((i % 64) as u32);
+    }
+    forward.min(reverse)
+}
+
+#[test]
+fn test_rolling_hashes() {
+    let fasta =
+        parse_fasta(b">a\nACGTTGCA\nTTAGNNACg\nTAcgtA\nCCGN\nA").expect("Failed to parse FASTA");
+    let seq = &fasta.sequences[0];
+    let residues = seq.copy_sequential();
+
+    for k in [1, 2, 3, 5, 7, 70] {
+        let expected = residues
+            .windows(k)
+            .filter(|kmer| kmer.iter().all(|&c| super::nt_seeds(c).is_some()))
+            .map(nt_hash)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            seq.rolling_hashes(k).collect::<Vec<_>>(),
+            expected,
+            "k = {k}"
+        );
+    }
+    assert_eq!(seq.rolling_hashes(0).count(), 0);
+}
+
+#[test]
+fn test_rolling_hashes_canonical() {
+    let fasta = parse_fasta(b">fwd\nGATTACAGATTACCA\n>rev\nTGGTAATCTGTAATC")
+        .expect("Failed to parse FASTA");
+
+    let forward = fasta.sequences[0].rolling_hashes(5).collect::<Vec<_>>();
+    let mut reverse = fasta.sequences[1].rolling_hashes(5).collect::<Vec<_>>();
+    reverse.reverse();
+    assert_eq!(forward, reverse);
+}
+
+#[test]
+fn test_kmers() {
+    let fasta = parse_fasta(b">a\nAC\n\nGTA\n>b\nC\n>c\n\n>d\nTTG").expect("Failed to parse FASTA");
+    let kmers = |seq: usize, k| {
+        fasta.sequences[seq]
+            .kmers(k)
+            .map(Vec::from)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        kmers(0, 3),
+        vec![b"ACG".to_vec(), b"CGT".to_vec(), b"GTA".to_vec()]
+    );
+    assert_eq!(kmers(0, 5), vec![b"ACGTA".to_vec()]);
+    assert!(kmers(0, 6).is_empty());
+    assert!(kmers(0, 0).is_empty());
+    assert!(kmers(2, 1).is_empty());
+
+    let all = fasta.all_kmers(2).map(Vec::from).collect::<Vec<_>>();
+    let expected = [&b"AC"[..], b"CG", b"GT", b"TA", b"TT", b"TG"].map(<[u8]>::to_vec);
+    assert_eq!(all, expected);
+    assert_eq!(fasta.all_kmers(1).count(), 9);
+}
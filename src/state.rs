@@ -0,0 +1,134 @@
+use crate::{record_at, FastaSequence, ParseError, ParseOptions, RecordRanges};
+
+/// A re-entrant parser for a FASTA buffer that grows over time,
+/// for example a memory-mapped file that is still being written,
+/// or a buffer that a framing layer appends chunks to.
+///
+/// Each call to [`resume`] receives the whole buffer, whose previously seen bytes must be unchanged,
+/// and returns the records that were completed since the last call.
+/// A record is complete once the start of the next record is seen,
+/// so the last record of the buffer is held back until more data arrives,
+/// or until [`finish`] is called at the end of the input.
+///
+/// ```rust
+/// # use fire_fasta::{ParseOptions, ParserState};
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let mut state = ParserState::new(ParseOptions::new());
+/// let mut buffer = b">Sample1\nAC".to_vec();
+///
+/// assert!(state.resume(&buffer)?.is_empty());
+///
+/// buffer.extend_from_slice(b"GT\n>Sample2\nAA");
+/// let records = state.resume(&buffer)?;
+/// assert_eq!(records.len(), 1);
+/// assert_eq!(records[0].copy_sequential().as_ref(), b"ACGT");
+///
+/// let records = state.finish(&buffer)?;
+/// assert_eq!(records[0].description, b"Sample2");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`resume`]: ParserState::resume
+/// [`finish`]: ParserState::finish
+#[derive(Clone, Debug, Default)]
+pub struct ParserState {
+    options: ParseOptions,
+    /// The offset of the first record that was not returned yet.
+    cursor: usize,
+    /// The number of records returned so far.
+    records: usize,
+}
+
+impl ParserState {
+    /// Create a parser state at the start of the input, which parses with the given options.
+    pub fn new(options: ParseOptions) -> Self {
+        Self {
+            options,
+            cursor: 0,
+            records: 0,
+        }
+    }
+
+    /// Returns the offset in the buffer up to which all records have been returned.
+    pub fn position(&self) -> usize {
+        self.cursor
+    }
+
+    /// Parse the buffer from where the last call stopped and return all records completed since.
+    /// The buffer must start with all bytes passed to previous calls.
+    /// An incomplete record at the end of the buffer is not returned,
+    /// and is parsed again by the next call.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`parse_fasta_with`],
+    /// except that a description at the end of the buffer without a sequence is not an error,
+    /// since the sequence may still arrive.
+    /// Since the rejected input remains in the buffer, all following calls return the error again.
+    ///
+    /// [`parse_fasta_with`]: crate::parse_fasta_with
+    pub fn resume<'a>(&mut self, data: &'a [u8]) -> Result<Vec<FastaSequence<'a>>, ParseError> {
+        self.parse(data, false)
+    }
+
+    /// Parse the rest of the buffer at the end of the input,
+    /// and return the remaining records, including the last record of the buffer.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`parse_fasta_with`].
+    ///
+    /// [`parse_fasta_with`]: crate::parse_fasta_with
+    pub fn finish(mut self, data: &[u8]) -> Result<Vec<FastaSequence<'_>>, ParseError> {
+        self.parse(data, true)
+    }
+
+    fn parse<'a>(
+        &mut self,
+        data: &'a [u8],
+        at_end: bool,
+    ) -> Result<Vec<FastaSequence<'a>>, ParseError> {
+        let mut sequences = Vec::new();
+        let data = data.get(self.cursor..).unwrap_or_default();
+
+        let mut consumed = 0;
+        let mut records = RecordRanges::with_options(data, self.options).spanned();
+        let remaining = self
+            .options
+            .max_records
+            .map_or(usize::MAX, |limit| limit.saturating_sub(self.records));
+        for record in records.by_ref().take(remaining) {
+            let record = match record {
+                // the description may still be followed by a sequence
+                Err(ParseError::EmptySequence) if !at_end => break,
+                record => record?,
+            };
+            let (span, record) = record;
+            if span.end == data.len() && !at_end {
+                break;
+            }
+
+            consumed = span.end;
+            // the span refers to the whole buffer, not only to the unparsed rest
+            let span = self.cursor + span.start..self.cursor + span.end;
+            sequences.push(record_at(data, (span, record), self.options));
+        }
+
+        if let Some(limit) = self.options.max_records {
+            // only look past the limit if it was reached and exceeding it is an error
+            if self.options.error_on_max_records
+                && sequences.len() == remaining
+                && records.next().is_some()
+            {
+                return Err(ParseError::TooManyRecords { limit });
+            }
+        }
+
+        self.cursor += consumed;
+        self.records += sequences.len();
+        Ok(sequences)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,73 @@
+use crate::{parse_fasta, parse_fasta_with, ParseError, ParseOptions, ParserState};
+
+#[test]
+fn test_resume_at_every_split() {
+    let data = b">a\nAC\nGT\n>b x\n\n>c\nT";
+    let expected = parse_fasta(data).expect("Failed to parse FASTA");
+
+    for split in 0..=data.len() {
+        let mut state = ParserState::new(ParseOptions::new());
+        let mut records = state.resume(&data[..split]).expect("Failed to resume");
+        records.extend(state.resume(data).expect("Failed to resume"));
+        records.extend(state.finish(data).expect("Failed to finish"));
+
+        let descriptions = records
+            .iter()
+            .map(|seq| seq.description)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            descriptions,
+            vec![&b"a"[..], b"b x", b"c"],
+            "split at {split}"
+        );
+        for (record, expected) in records.iter().zip(&expected.sequences) {
+            assert_eq!(record.copy_sequential(), expected.copy_sequential());
+            assert_eq!(record.span, expected.span, "split at {split}");
+        }
+    }
+}
+
+#[test]
+fn test_resume_holds_back_incomplete_records() {
+    let mut state = ParserState::new(ParseOptions::new());
+
+    assert!(state.resume(b">a").expect("Failed to resume").is_empty());
+    assert!(state.resume(b">a\n").expect("Failed to resume").is_empty());
+    assert!(state
+        .resume(b">a\nAC\n")
+        .expect("Failed to resume")
+        .is_empty());
+    assert_eq!(state.position(), 0);
+
+    let records = state.resume(b">a\nAC\n>").expect("Failed to resume");
+    assert_eq!(records.len(), 1);
+    assert_eq!(state.position(), 6);
+
+    assert!(matches!(
+        state.finish(b">a\nAC\n>b\n"),
+        Err(ParseError::EmptySequence)
+    ));
+}
+
+#[test]
+fn test_resume_errors_and_limits() {
+    let mut state = ParserState::new(ParseOptions::new());
+    assert!(matches!(
+        state.resume(b"AC"),
+        Err(ParseError::InvalidDescription { invalid: b'A' })
+    ));
+
+    let data = b">a\nA\n>b\nC\n>c\nG\n>";
+    let options = ParseOptions::new().max_records(2);
+    let mut state = ParserState::new(options);
+    assert_eq!(state.resume(&data[..8]).expect("Failed to resume").len(), 1);
+    assert_eq!(state.resume(data).expect("Failed to resume").len(), 1);
+    assert!(state.finish(data).expect("Failed to finish").is_empty());
+
+    let mut state = ParserState::new(options.error_on_max_records(true));
+    assert!(matches!(
+        state.resume(data),
+        Err(ParseError::TooManyRecords { limit: 2 })
+    ));
+    assert!(parse_fasta_with(data, options.error_on_max_records(true)).is_err());
+}
@@ -0,0 +1,82 @@
+use crate::{count_newlines, Fasta, Newline};
+
+/// Summary statistics of a FASTA file, computed by [`Fasta::stats`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct FastaStats {
+    /// The number of records.
+    pub records: usize,
+
+    /// The total number of residues of all sequences, excluding newlines.
+    pub residues: usize,
+
+    /// The total number of bytes of all sequences, including newlines.
+    /// Descriptions are not included,
+    /// see [`Fasta::estimated_bytes`] for the size of the full records in the input.
+    pub sequence_bytes: usize,
+
+    /// The mean number of residues per record, or `0.0` if there are no records.
+    pub mean_residues: f64,
+
+    /// The fraction of the sequence bytes that are newlines (`LF`),
+    /// or `0.0` if there are no sequence bytes.
+    pub newline_fraction: f64,
+}
+
+impl<'a> Fasta<'a> {
+    /// Compute [summary statistics](FastaStats) of the file in one scan over the sequences.
+    /// The residues are derived from the newline count, except for sequences parsed with
+    /// [`Newline::Auto`] or [`strip_sequence_spaces`], which need a second scan to find the
+    /// skipped `CR`s and spaces.
+    ///
+    /// [`strip_sequence_spaces`]: crate::ParseOptions::strip_sequence_spaces
+    ///
+    /// ```rust
+    /// # use fire_fasta::parse_fasta;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">a\nACG\nT\n>b\nAC\n")?;
+    /// let stats = fasta.stats();
+    ///
+    /// assert_eq!((stats.records, stats.residues, stats.sequence_bytes), (2, 6, 9));
+    /// assert_eq!(stats.mean_residues, 3.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stats(&self) -> FastaStats {
+        let records = self.sequences.len();
+        let mut residues = 0;
+        let mut sequence_bytes = 0;
+        let mut newlines = 0;
+        for seq in &self.sequences {
+            let seq_newlines = count_newlines(seq.sequence);
+            residues += if seq.newline == Newline::Lf && !seq.strip_spaces {
+                seq.sequence.len() - seq_newlines
+            } else {
+                seq.len()
+            };
+            sequence_bytes += seq.sequence.len();
+            newlines += seq_newlines;
+        }
+
+        FastaStats {
+            records,
+            residues,
+            sequence_bytes,
+            mean_residues: ratio(residues, records),
+            newline_fraction: ratio(newlines, sequence_bytes),
+        }
+    }
+}
+
+/// Returns `numerator / denominator`, or `0.0` if the denominator is zero.
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+#[cfg(test)]
+mod tests;
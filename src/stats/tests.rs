@@ -0,0 +1,29 @@
+use crate::{parse_fasta, parse_fasta_with, Newline, ParseOptions};
+
+#[test]
+fn test_stats() {
+    let fasta = parse_fasta(b">a\nACGT\nAC\n\n>b\nG\n>c\nTTA").expect("Failed to parse FASTA");
+    let stats = fasta.stats();
+
+    assert_eq!(stats.records, 3);
+    assert_eq!(stats.residues, 10);
+    assert_eq!(stats.sequence_bytes, 14);
+    assert_eq!(stats.mean_residues, 10.0 / 3.0);
+    assert_eq!(stats.newline_fraction, 4.0 / 14.0);
+
+    let options = ParseOptions::new().newline(Newline::Auto);
+    let stats = parse_fasta_with(b">a\nAC\r\nG", options).unwrap().stats();
+    assert_eq!((stats.residues, stats.sequence_bytes), (3, 5));
+
+    let options = ParseOptions::new().strip_sequence_spaces(true);
+    let stats = parse_fasta_with(b">a\nAC G\nT", options).unwrap().stats();
+    assert_eq!((stats.residues, stats.sequence_bytes), (4, 6));
+
+    let stats = parse_fasta(b"").unwrap().stats();
+    assert_eq!(
+        (stats.records, stats.residues, stats.sequence_bytes),
+        (0, 0, 0)
+    );
+    assert_eq!(stats.mean_residues, 0.0);
+    assert_eq!(stats.newline_fraction, 0.0);
+}
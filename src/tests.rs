@@ -1,15 +1,22 @@
-use crate::parse_fasta_str;
+use crate::{
+    count_records, parse_fasta, parse_fasta_bounded, parse_fasta_lenient, parse_fasta_recovering,
+    parse_fasta_str, parse_fasta_utf8_checked, parse_fasta_validated, parse_fasta_with,
+    parse_headers_only, parse_single_fasta, percent_identity, FastaSequence, Newline, ParseError,
+    ParseOptions, ParseWarning, RecordStart,
+};
+use std::borrow::Cow;
+use std::collections::HashSet;
 
 #[test]
 fn empty_fasta() {
     let empty = "";
-    assert!(parse_fasta_str(&empty).unwrap().sequences.is_empty());
+    assert!(parse_fasta_str(empty).unwrap().sequences.is_empty());
 }
 
 #[test]
 fn empty_description() {
     let empty = ">\nA";
-    let fasta = parse_fasta_str(&empty).unwrap();
+    let fasta = parse_fasta_str(empty).unwrap();
     assert_eq!(fasta.sequences.len(), 1);
 
     let seq = &fasta.sequences[0];
@@ -25,7 +32,7 @@ MSTILEKISAIESEMARTQKNKATSAHLGLLKAKLAKLRRELISPKGGGGGTGEAGFEVAKTGDARVGFVGFPSVGKSTL
     "#
     .trim();
 
-    let fasta = parse_fasta_str(&seq).expect("Failed to parse FASTA");
+    let fasta = parse_fasta_str(seq).expect("Failed to parse FASTA");
     assert_eq!(fasta.sequences.len(), 1);
     assert_eq!(fasta.sequences[0].description, b"P32234 1-368");
     assert_eq!(
@@ -45,7 +52,7 @@ MQKINNINNNKQMLTRKEDLLTVLKQISALKYVSNLYEFLLATEKIVQTSELDTQFQEFLTTTIIASEQNLVENYKQKYN
     "
     .trim();
 
-    let fasta = parse_fasta_str(&seq).expect("Failed to parse FASTA");
+    let fasta = parse_fasta_str(seq).expect("Failed to parse FASTA");
     assert_eq!(fasta.sequences.len(), 2);
 
     assert_eq!(fasta.sequences[0].description, b"P32234 1-368");
@@ -74,7 +81,7 @@ ASEQNLVENYKQKYN
     "
     .trim();
 
-    let fasta = parse_fasta_str(&seq).expect("Failed to parse FASTA");
+    let fasta = parse_fasta_str(seq).expect("Failed to parse FASTA");
     assert_eq!(fasta.sequences.len(), 1);
 
     assert_eq!(fasta.sequences[0].description, b"P32234 1-368");
@@ -85,8 +92,1329 @@ ASEQNLVENYKQKYN
 #[test]
 fn test_copy_sequential() {
     let seq = ">ABCD\nATG\nGTA\nCCC\nCGC\nAT";
-    let fasta = parse_fasta_str(&seq).expect("Failed to parse FASTA");
+    let fasta = parse_fasta_str(seq).expect("Failed to parse FASTA");
 
     let copied = fasta.sequences[0].copy_sequential();
     assert_eq!(copied.as_ref(), b"ATGGTACCCCGCAT");
 }
+
+#[test]
+fn test_sort_by_id() {
+    let seq = ">C third\nAA\n>A first\nAAAA\n>B second\nA";
+    let mut fasta = parse_fasta_str(seq).expect("Failed to parse FASTA");
+
+    fasta.sort_by_id();
+    let ids = fasta.sequences.iter().map(|s| s.id()).collect::<Vec<_>>();
+    assert_eq!(ids, vec![&b"A"[..], b"B", b"C"]);
+}
+
+#[test]
+fn test_sort_by_length() {
+    let seq = ">short\nAC\n>long\nACG\nTAC\nGT\n>mid\nACGTA\n>tie\nAC";
+    let mut fasta = parse_fasta_str(seq).expect("Failed to parse FASTA");
+
+    fasta.sort_by_length();
+    let ids = fasta.sequences.iter().map(|s| s.id()).collect::<Vec<_>>();
+    assert_eq!(ids, vec![&b"long"[..], b"mid", b"short", b"tie"]);
+}
+
+#[test]
+fn test_binary_search_id() {
+    let seq = ">C\nAA\n>A\nAAAA\n>E\nA\n>B\nA";
+    let mut fasta = parse_fasta_str(seq).expect("Failed to parse FASTA");
+    fasta.sort_by_id();
+
+    assert_eq!(fasta.binary_search_id(b"A"), Ok(0));
+    assert_eq!(fasta.binary_search_id(b"E"), Ok(3));
+    assert_eq!(fasta.binary_search_id(b"D"), Err(3));
+    assert_eq!(fasta.binary_search_id(b"0"), Err(0));
+}
+
+#[test]
+fn test_parse_headers_only() {
+    let seq = b">first seq\nACGT\nAC\n>second\nGG\n";
+    let headers = parse_headers_only(seq).expect("Failed to parse FASTA");
+    assert_eq!(headers, vec![(&b"first seq"[..], 0), (&b"second"[..], 19)]);
+
+    assert!(parse_headers_only(b"").unwrap().is_empty());
+    assert!(matches!(
+        parse_headers_only(b">a\nA\n>b\n"),
+        Err(ParseError::EmptySequence)
+    ));
+    assert!(matches!(
+        parse_headers_only(b"A\n"),
+        Err(ParseError::InvalidDescription { invalid: b'A' })
+    ));
+}
+
+#[test]
+fn test_parse_fasta_validated() {
+    let mut protein = [false; 256];
+    for &residue in b"ACDEFGHIKLMNPQRSTVWY" {
+        protein[residue as usize] = true;
+    }
+
+    let seq = b">P32234 1-368\nMSTILEKIS\nAIESEM\n\n>O77448 1-1117\nMQKINN\n";
+    let fasta = parse_fasta_validated(seq, &protein).expect("Failed to parse FASTA");
+    assert_eq!(fasta.sequences.len(), 2);
+    assert_eq!(fasta.sequences[1].copy_sequential().as_ref(), b"MQKINN");
+
+    let seq = b">P32234 1-368\nMSTILEKIS\nAIESEM\n>O77448 1-1117\nMQKI*\n";
+    assert!(matches!(
+        parse_fasta_validated(seq, &protein),
+        Err(ParseError::InvalidResidue {
+            byte: b'*',
+            offset: 50
+        })
+    ));
+}
+
+#[test]
+fn test_trailing_newline() {
+    assert!(parse_fasta_str(">a\nAC\n>b\nGT\n")
+        .unwrap()
+        .trailing_newline());
+    assert!(!parse_fasta_str(">a\nAC\n>b\nGT")
+        .unwrap()
+        .trailing_newline());
+    assert!(!parse_fasta_str(">a\nAC\n>b\n\nGT")
+        .unwrap()
+        .trailing_newline());
+    assert!(!parse_fasta_str("").unwrap().trailing_newline());
+}
+
+#[test]
+fn test_max_records() {
+    let seq = b">a\nAC\n>b\nGT\n>c\nTT\n";
+
+    let mut options = ParseOptions {
+        max_records: Some(2),
+        ..Default::default()
+    };
+    let fasta = parse_fasta_with(seq, options).expect("Failed to parse FASTA");
+    assert_eq!(fasta.sequences.len(), 2);
+    assert_eq!(fasta.sequences[1].description, b"b");
+
+    options.error_on_max_records = true;
+    assert!(matches!(
+        parse_fasta_with(seq, options),
+        Err(ParseError::TooManyRecords { limit: 2 })
+    ));
+
+    options.max_records = Some(3);
+    let fasta = parse_fasta_with(seq, options).expect("Failed to parse FASTA");
+    assert_eq!(fasta.sequences.len(), 3);
+
+    options.max_records = Some(0);
+    assert!(parse_fasta_with(b"", options).unwrap().sequences.is_empty());
+
+    // records after the limit are not parsed, so they may be malformed
+    options.max_records = Some(1);
+    options.error_on_max_records = false;
+    let fasta = parse_fasta_with(b">a\nAC\n>b", options).expect("Failed to parse FASTA");
+    assert_eq!(fasta.sequences.len(), 1);
+    options.error_on_max_records = true;
+    assert!(matches!(
+        parse_fasta_with(b">a\nAC\n>b", options),
+        Err(ParseError::TooManyRecords { limit: 1 })
+    ));
+}
+
+#[test]
+fn test_filter_by_sequence() {
+    let fasta = parse_fasta_str(">a\nACNNNN\n>b\nAC\nGT\n>c\nA").expect("Failed to parse FASTA");
+
+    let filtered = fasta
+        .filter_by_sequence(|seq| seq.len() > 1 && !seq.iter().any(|&c| c == b'N'))
+        .map(|seq| seq.description)
+        .collect::<Vec<_>>();
+    assert_eq!(filtered, vec![b"b"]);
+}
+
+#[test]
+fn test_parse_fasta_lenient() {
+    let (fasta, warnings) = parse_fasta_lenient(b"\n\n>a\nAC\n>\n\n\n>c\nG\r\n>d");
+    let descriptions = fasta
+        .sequences
+        .iter()
+        .map(|seq| seq.description)
+        .collect::<Vec<_>>();
+    assert_eq!(descriptions, vec![&b"a"[..], b"", b"c", b"d"]);
+    assert_eq!(
+        warnings,
+        vec![
+            ParseWarning::UnexpectedLeadingBytes { count: 2 },
+            ParseWarning::EmptyDescription { index: 1 },
+            ParseWarning::EmptySequence { index: 1 },
+            ParseWarning::ContainsCarriageReturn { index: 2 },
+            ParseWarning::EmptySequence { index: 3 },
+        ]
+    );
+
+    let (fasta, warnings) = parse_fasta_lenient(b">a\nACGT\n>b\nGT\n");
+    assert_eq!(fasta.sequences.len(), 2);
+    assert!(warnings.is_empty());
+
+    let (fasta, warnings) = parse_fasta_lenient(b"ACGT");
+    assert!(fasta.sequences.is_empty());
+    assert_eq!(
+        warnings,
+        vec![ParseWarning::UnexpectedLeadingBytes { count: 4 }]
+    );
+}
+
+#[test]
+fn test_parse_options_builder() {
+    let options = ParseOptions::new()
+        .max_records(2)
+        .error_on_max_records(true);
+    assert_eq!(options.max_records, Some(2));
+    assert!(options.error_on_max_records);
+
+    // default options must parse exactly like parse_fasta
+    let seq = b">a desc\nAC\nGT>b\n\n>c\nTT\n";
+    let default = parse_fasta_with(seq, Default::default()).expect("Failed to parse FASTA");
+    let plain = parse_fasta_str(std::str::from_utf8(seq).unwrap()).unwrap();
+    assert_eq!(default.sequences.len(), plain.sequences.len());
+    for (a, b) in default.sequences.iter().zip(plain.sequences.iter()) {
+        assert_eq!(a.description, b.description);
+        assert_eq!(a.sequence, b.sequence);
+    }
+}
+
+#[test]
+fn test_lines() {
+    let fasta = parse_fasta_str(">a\nACG\nTA\n\nC\n\n").expect("Failed to parse FASTA");
+    let seq = &fasta.sequences[0];
+
+    assert_eq!(
+        seq.lines().collect::<Vec<_>>(),
+        vec![&b"ACG"[..], b"TA", b"C"]
+    );
+    assert_eq!(seq.line_count(), 3);
+}
+
+#[test]
+fn test_detect_line_width() {
+    let wrapped = ">a\nACGT\nACGT\nAC\n>b\nACG\n>c\nACGT\nACGT\n";
+    let fasta = parse_fasta_str(wrapped).expect("Failed to parse FASTA");
+    assert_eq!(fasta.detect_line_width(), Some(4));
+
+    let mixed = ">a\nACGT\nACGT\nAC\n>b\nACG\nACG\n";
+    let fasta = parse_fasta_str(mixed).expect("Failed to parse FASTA");
+    assert_eq!(fasta.detect_line_width(), None);
+
+    let long_last_line = ">a\nACGT\nACGT\n>b\nACGTACGT\n";
+    let fasta = parse_fasta_str(long_last_line).expect("Failed to parse FASTA");
+    assert_eq!(fasta.detect_line_width(), None);
+
+    let unwrapped = ">a\nACGT\n>b\nACGTACGT\n";
+    let fasta = parse_fasta_str(unwrapped).expect("Failed to parse FASTA");
+    assert_eq!(fasta.detect_line_width(), None);
+}
+
+#[test]
+fn test_index_fasta() {
+    let fasta = parse_fasta_str(">a\nAC\n>b\nGT").expect("Failed to parse FASTA");
+    assert_eq!(fasta[0].description, b"a");
+    assert_eq!(fasta[1].description, b"b");
+
+    let slice: &[FastaSequence] = fasta.as_ref();
+    assert_eq!(slice.len(), 2);
+}
+
+#[test]
+#[should_panic]
+fn test_index_fasta_out_of_bounds() {
+    let fasta = parse_fasta_str(">a\nAC").expect("Failed to parse FASTA");
+    let _ = &fasta[1];
+}
+
+#[test]
+fn test_newline_offsets() {
+    let fasta = parse_fasta_str(">a\nACG\nTA\n\nC>b\nACGT>c\nAC\n").expect("Failed to parse FASTA");
+    assert_eq!(fasta[0].newline_offsets(), vec![3, 6, 7]);
+    assert!(fasta[1].newline_offsets().is_empty());
+    assert_eq!(fasta[2].newline_offsets(), vec![2]);
+}
+
+#[test]
+fn test_newline_auto() {
+    let seq = b">a desc\r\nAC\r\nGT\nT\rA\r\n\r\n>b\nAC\nG\r";
+    let fasta = parse_fasta_with(seq, ParseOptions::new().newline(Newline::Auto))
+        .expect("Failed to parse FASTA");
+
+    assert_eq!(fasta[0].description, b"a desc");
+    assert_eq!(fasta[0].copy_sequential().as_ref(), b"ACGTT\rA");
+    assert_eq!(
+        fasta[0].iter().copied().collect::<Vec<_>>(),
+        b"ACGTT\rA".to_vec()
+    );
+    assert_eq!(fasta[0].len(), 7);
+    assert_eq!(
+        fasta[0].lines().collect::<Vec<_>>(),
+        vec![&b"AC"[..], b"GT", b"T\rA"]
+    );
+
+    // a CR at the very end is not followed by a LF, and thus preserved
+    assert_eq!(fasta[1].description, b"b");
+    assert_eq!(fasta[1].copy_sequential().as_ref(), b"ACG\r");
+    assert_eq!(fasta[1].len(), 4);
+
+    let fasta = parse_fasta_with(seq, ParseOptions::new()).expect("Failed to parse FASTA");
+    assert_eq!(fasta[0].description, b"a desc\r");
+    assert_eq!(fasta[0].copy_sequential().as_ref(), b"AC\rGTT\rA\r\r");
+}
+
+#[test]
+fn test_to_string() {
+    let fasta = parse_fasta_str(">a\nMST\nIL\n").expect("Failed to parse FASTA");
+    assert_eq!(fasta[0].to_string().unwrap(), "MSTIL");
+    assert_eq!(fasta[0].to_string_lossy(), "MSTIL");
+
+    let fasta = parse_fasta_with(b">a\nMS\xff\nIL\n", ParseOptions::new()).unwrap();
+    assert_eq!(fasta[0].to_string().unwrap_err().valid_up_to(), 2);
+    assert_eq!(fasta[0].to_string_lossy(), "MS\u{FFFD}IL");
+}
+
+/// Exhaustively parse all short inputs over an alphabet of structurally relevant characters,
+/// making sure no entry point panics and sequence accessors agree with each other.
+/// The fuzz targets in `fuzz/` do the same for arbitrary inputs.
+#[test]
+fn test_no_panic_on_short_inputs() {
+    const ALPHABET: &[u8] = b">\n\rA ";
+    const MAX_LEN: u32 = 7;
+
+    fn check_sequence(seq: &FastaSequence) {
+        let copied = seq.copy_sequential();
+        assert_eq!(seq.len(), copied.len());
+        assert_eq!(seq.iter().count(), copied.len());
+        assert_eq!(seq.is_empty(), copied.is_empty());
+        assert!(seq.iter_rev().eq(copied.iter().rev()));
+        let _ = format!("{seq:?}");
+        let _ = (seq.id(), seq.line_count(), seq.newline_offsets());
+        let _ = (seq.to_string_lossy(), seq.to_rna(), seq.codons(1).count());
+    }
+
+    let mut allowed = [false; 256];
+    allowed[b'A' as usize] = true;
+
+    for len in 0..=MAX_LEN {
+        for n in 0..ALPHABET.len().pow(len) {
+            let data = (0..len)
+                .map(|i| ALPHABET[n / ALPHABET.len().pow(i) % ALPHABET.len()])
+                .collect::<Vec<_>>();
+
+            for options in [
+                ParseOptions::new(),
+                ParseOptions::new().newline(Newline::Auto).max_records(1),
+                ParseOptions::strict(),
+                ParseOptions::strict().assume_leading_sequence(true),
+                ParseOptions::new().max_header_len(1),
+                ParseOptions::new()
+                    .trim_descriptions(true)
+                    .assume_leading_sequence(true),
+                ParseOptions::new()
+                    .blank_line_separator(true)
+                    .newline(Newline::Auto),
+                ParseOptions::new()
+                    .strip_sequence_spaces(true)
+                    .newline(Newline::Auto),
+            ] {
+                if let Ok(fasta) = parse_fasta_with(&data, options) {
+                    fasta.sequences.iter().for_each(check_sequence);
+                    assert_eq!(fasta.records_with_spans().count(), fasta.sequences.len());
+                    let _ = (fasta.trailing_newline(), fasta.detect_line_width());
+                }
+            }
+
+            let (fasta, errors) = parse_fasta_recovering(&data);
+            fasta.sequences.iter().for_each(check_sequence);
+            assert!(errors.windows(2).all(|pair| pair[0].0 < pair[1].0));
+            let (lenient, _) = parse_fasta_lenient(&data);
+            lenient.sequences.iter().for_each(check_sequence);
+            if let Ok(fasta) = parse_fasta(&data) {
+                assert_eq!(lenient.sequences, fasta.sequences);
+            }
+            if let Ok(fasta) = parse_fasta_validated(&data, &allowed) {
+                fasta.sequences.iter().for_each(check_sequence);
+            }
+            // with every byte allowed, validation doesn't change the result of parsing
+            assert_eq!(
+                parse_fasta_validated(&data, &[true; 256])
+                    .map(|fasta| fasta.sequences)
+                    .ok(),
+                parse_fasta(&data).map(|fasta| fasta.sequences).ok()
+            );
+            let _ = parse_headers_only(&data);
+        }
+    }
+}
+
+#[test]
+fn test_skip_leading_blank_lines() {
+    let options = ParseOptions::new().skip_leading_blank_lines(true);
+
+    let fasta = parse_fasta_with(b"\n\n \t\r\n>a\nAC\n", options).expect("Failed to parse FASTA");
+    assert_eq!(fasta.sequences.len(), 1);
+    assert_eq!(fasta[0].description, b"a");
+
+    let fasta = parse_fasta_with(b"\n\n\n", options).expect("Failed to parse FASTA");
+    assert!(fasta.sequences.is_empty());
+
+    assert!(matches!(
+        parse_fasta_with(b"\n\nAC\n", options),
+        Err(ParseError::InvalidDescription { invalid: b'A' })
+    ));
+    assert!(matches!(
+        parse_fasta_with(b"\n\n>a\nAC\n", ParseOptions::new()),
+        Err(ParseError::InvalidDescription { invalid: b'\n' })
+    ));
+}
+
+#[test]
+fn test_record_start_line_start() {
+    let data = b">a\nAC>GT\nA>\n>b x\n>c\nT";
+
+    let fasta = parse_fasta_with(data, ParseOptions::strict()).expect("Failed to parse FASTA");
+    assert_eq!(fasta.sequences.len(), 3);
+    assert_eq!(fasta[0].copy_sequential().as_ref(), b"AC>GTA>");
+    assert_eq!(fasta[1].description, b"b x");
+    assert!(fasta[1].is_empty());
+    assert_eq!(fasta[2].copy_sequential().as_ref(), b"T");
+
+    let fasta = parse_fasta_with(data, ParseOptions::new()).expect("Failed to parse FASTA");
+    assert_eq!(fasta.sequences.len(), 5);
+
+    let options = ParseOptions::new().record_start(RecordStart::LineStart);
+    assert!(matches!(
+        parse_fasta_with(b">a\nAC\n>b\n", options),
+        Err(ParseError::EmptySequence)
+    ));
+}
+
+#[test]
+fn test_join_residues() {
+    let fasta = parse_fasta_str(">a\nAC\nGT\n\n>b\n\n>c\nTTA").expect("Failed to parse FASTA");
+    assert_eq!(fasta.join_residues(b'$'), b"ACGT$$TTA");
+
+    let fasta = parse_fasta_str("").unwrap();
+    assert!(fasta.join_residues(b'$').is_empty());
+}
+
+#[test]
+fn test_count_records() {
+    assert_eq!(count_records(b"").unwrap(), 0);
+    assert_eq!(count_records(b">a\nAC\n>b\n\n>c\nT").unwrap(), 3);
+    assert_eq!(count_records(b">a>b\nAC\n").unwrap(), 1);
+    assert!(matches!(
+        count_records(b"AC\n"),
+        Err(ParseError::InvalidDescription { invalid: b'A' })
+    ));
+    assert!(matches!(
+        count_records(b">a\nAC\n>b\n"),
+        Err(ParseError::EmptySequence)
+    ));
+}
+
+#[test]
+fn test_iter_rev() {
+    let data = b">a\nAC\r\nGT\n\nTA\r\n>b\n>c\nA\r";
+    for options in [
+        ParseOptions::new(),
+        ParseOptions::new().newline(Newline::Auto),
+    ] {
+        let fasta = parse_fasta_with(data, options).expect("Failed to parse FASTA");
+        for seq in &fasta.sequences {
+            let reversed = seq.iter_rev().copied().collect::<Vec<_>>();
+            assert_eq!(reversed, seq.reverse().as_ref());
+            assert_eq!(reversed, seq.iter().rev().copied().collect::<Vec<_>>());
+        }
+    }
+
+    let fasta = parse_fasta_str(">a\nACG\nT").expect("Failed to parse FASTA");
+    assert_eq!(fasta[0].reverse().as_ref(), b"TGCA");
+}
+
+#[test]
+fn test_contains() {
+    let data = b">a\nACGTA\r\nCCGT\n>b\nTTAGC\r\n>c\nAC\n\n";
+    for options in [
+        ParseOptions::new(),
+        ParseOptions::new().newline(Newline::Auto),
+    ] {
+        let fasta = parse_fasta_with(data, options).expect("Failed to parse FASTA");
+        for seq in &fasta.sequences {
+            let residues = seq.copy_sequential();
+            for start in 0..residues.len() {
+                for end in start..=residues.len() {
+                    assert!(seq.contains(&residues[start..end]));
+                }
+            }
+        }
+
+        assert_eq!(fasta[0].contains(b"TACC"), options.newline == Newline::Auto);
+        assert!(!fasta[1].contains(b"GCT"));
+        assert!(fasta[2].contains(b""));
+    }
+
+    let fasta = parse_fasta_str(">a\nACGTA\nCCGT").expect("Failed to parse FASTA");
+    assert!(fasta[0].contains(b"TACC"));
+    assert!(!fasta[0].contains(b"TAC\nC"));
+}
+
+#[test]
+fn test_position_translation() {
+    let data = b">a\nAC\r\nGT\n\nTA\r\n>b\n>c\nA\r";
+    for options in [
+        ParseOptions::new(),
+        ParseOptions::new().newline(Newline::Auto),
+    ] {
+        let fasta = parse_fasta_with(data, options).expect("Failed to parse FASTA");
+        for seq in &fasta.sequences {
+            let residues = seq.copy_sequential();
+            for logical in 0..residues.len() {
+                let physical = seq.logical_to_physical(logical).unwrap();
+                assert_eq!(seq.sequence[physical], residues[logical]);
+                assert_eq!(seq.physical_to_logical(physical), Some(logical));
+            }
+            assert_eq!(seq.logical_to_physical(residues.len()), None);
+
+            let mapped = (0..seq.sequence.len() + 1)
+                .filter(|&physical| seq.physical_to_logical(physical).is_some())
+                .count();
+            assert_eq!(mapped, residues.len());
+        }
+    }
+}
+
+#[test]
+fn test_as_residues_unchecked() {
+    let fasta = parse_fasta_str(">a\nACGT\n>b\nAC\r\n>c\nTT").expect("Failed to parse FASTA");
+    assert_eq!(fasta[0].as_residues_unchecked(), b"ACGT");
+    assert_eq!(fasta[1].as_residues_unchecked(), b"AC\r");
+    assert_eq!(fasta[2].as_residues_unchecked(), b"TT");
+
+    let options = ParseOptions::new().newline(Newline::Auto);
+    let fasta = parse_fasta_with(b">b\nAC\r\n", options).expect("Failed to parse FASTA");
+    assert_eq!(fasta[0].as_residues_unchecked(), b"AC");
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "internal newlines")]
+fn test_as_residues_unchecked_multi_line() {
+    let fasta = parse_fasta_str(">a\nAC\nGT\n").expect("Failed to parse FASTA");
+    let _ = fasta[0].as_residues_unchecked();
+}
+
+#[test]
+fn test_assume_leading_sequence() {
+    let options = ParseOptions::new().assume_leading_sequence(true);
+
+    let fasta = parse_fasta_with(b"GT\nAC\n>b\nTT", options).expect("Failed to parse FASTA");
+    assert_eq!(fasta.sequences.len(), 2);
+    assert_eq!(fasta[0].description, b"");
+    assert_eq!(fasta[0].copy_sequential().as_ref(), b"GTAC");
+    assert_eq!(fasta[1].description, b"b");
+
+    let fasta = parse_fasta_with(b"A>C\n>b\nTT", options.record_start(RecordStart::LineStart))
+        .expect("Failed to parse FASTA");
+    assert_eq!(fasta[0].copy_sequential().as_ref(), b"A>C");
+    assert_eq!(fasta[1].description, b"b");
+
+    let fasta = parse_fasta_with(b">a\nAC", options).expect("Failed to parse FASTA");
+    assert_eq!(fasta[0].description, b"a");
+    let fasta = parse_fasta_with(b"ACGT", options).expect("Failed to parse FASTA");
+    assert_eq!(fasta.sequences.len(), 1);
+    assert!(parse_fasta_with(b"", options).unwrap().sequences.is_empty());
+}
+
+#[test]
+fn test_iterate_records() {
+    let fasta = parse_fasta_str(">a\nAC\n>b\nGT\n>c\nTT").expect("Failed to parse FASTA");
+
+    let mut records = (&fasta).into_iter();
+    assert_eq!(records.len(), 3);
+    records.next();
+    assert_eq!(records.len(), 2);
+
+    let descriptions = fasta.iter().map(|seq| seq.description).collect::<Vec<_>>();
+    assert_eq!(descriptions, vec![&b"a"[..], b"b", b"c"]);
+}
+
+#[test]
+fn test_residues() {
+    let data = b">a\nACGT\r\n>b\nAC\r\nGT\n>c\nTT\n\n>d\nA";
+    for options in [
+        ParseOptions::new(),
+        ParseOptions::new().newline(Newline::Auto),
+    ] {
+        let fasta = parse_fasta_with(data, options).expect("Failed to parse FASTA");
+        for seq in &fasta.sequences {
+            assert_eq!(seq.residues().as_ref(), seq.copy_sequential().as_ref());
+        }
+
+        let borrowed = fasta
+            .iter()
+            .map(|seq| matches!(seq.residues(), Cow::Borrowed(_)))
+            .collect::<Vec<_>>();
+        assert_eq!(borrowed, vec![true, false, false, true]);
+    }
+}
+
+#[test]
+fn test_parse_fasta_recovering() {
+    let (fasta, errors) = parse_fasta_recovering(b">a\nAC\n>b\nGT\n");
+    assert_eq!(fasta.sequences.len(), 2);
+    assert!(errors.is_empty());
+
+    let (fasta, errors) = parse_fasta_recovering(b"garbage\nmore\n>a\nAC\n>b\nGT\n>c");
+    let descriptions = fasta.iter().map(|seq| seq.description).collect::<Vec<_>>();
+    assert_eq!(descriptions, vec![&b"a"[..], b"b"]);
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(
+        errors[0],
+        (0, ParseError::InvalidDescription { invalid: b'g' })
+    ));
+    assert!(matches!(errors[1], (25, ParseError::EmptySequence)));
+
+    let (fasta, errors) = parse_fasta_recovering(b"garbage");
+    assert!(fasta.sequences.is_empty());
+    assert_eq!(errors.len(), 1);
+
+    let (fasta, errors) = parse_fasta_recovering(b"");
+    assert!(fasta.sequences.is_empty() && errors.is_empty());
+}
+
+#[test]
+fn test_debug_truncates_sequence() {
+    let data = format!(
+        ">long seq\n{}\n{}\n>short\nAC\nGT",
+        "A".repeat(40),
+        "C".repeat(40)
+    );
+    let fasta = parse_fasta_str(&data).expect("Failed to parse FASTA");
+
+    assert_eq!(
+        format!("{:?}", fasta[0]),
+        format!(
+            "FastaSequence {{ description: \"long seq\", sequence: \"{}...{}\", len: 80 }}",
+            "A".repeat(16),
+            "C".repeat(16)
+        )
+    );
+    assert_eq!(
+        format!("{:?}", fasta[1]),
+        "FastaSequence { description: \"short\", sequence: \"ACGT\", len: 4 }"
+    );
+}
+
+#[test]
+fn test_length_percentiles() {
+    let fasta = parse_fasta_str(">a\nAC\nGT\n>b\nA\n>c\nACGTAC\n>d\n\n>e\nACG")
+        .expect("Failed to parse FASTA");
+
+    assert_eq!(fasta.length_percentile(0.0), Some(0));
+    assert_eq!(fasta.length_percentile(0.5), Some(3));
+    assert_eq!(fasta.length_percentile(1.0), Some(6));
+    assert_eq!(fasta.length_percentile(0.9), Some(5));
+    assert_eq!(fasta.length_quartiles(), Some([1, 3, 4]));
+
+    assert_eq!(fasta.length_percentile(1.5), None);
+    assert_eq!(fasta.length_percentile(-0.1), None);
+    assert_eq!(fasta.length_percentile(f64::NAN), None);
+
+    let fasta = parse_fasta_str("").unwrap();
+    assert_eq!(fasta.length_percentile(0.5), None);
+    assert_eq!(fasta.length_quartiles(), None);
+}
+
+#[test]
+fn test_dirty_descriptions() {
+    let data = ">clean desc\nAC\n>tab\tdesc\nGT\n>bell\x07\nT\n>cr\r\nA\n>\nC";
+    let fasta = parse_fasta_str(data).expect("Failed to parse FASTA");
+    assert!(fasta[0].description_is_clean());
+    assert!(fasta[4].description_is_clean());
+    assert_eq!(fasta.dirty_descriptions(), vec![1, 2, 3]);
+
+    let options = ParseOptions::new().newline(Newline::Auto);
+    let fasta = parse_fasta_with(data.as_bytes(), options).expect("Failed to parse FASTA");
+    assert_eq!(fasta.dirty_descriptions(), vec![1, 2]);
+}
+
+#[test]
+fn test_select_ids() {
+    let fasta =
+        parse_fasta_str(">b x\nAC\n>a\nGT\n>c\nTT\n>b y\nGG").expect("Failed to parse FASTA");
+
+    let ids = HashSet::from([&b"b"[..], b"c", b"missing"]);
+    let selected = fasta
+        .select_ids(&ids)
+        .into_iter()
+        .map(|seq| seq.description)
+        .collect::<Vec<_>>();
+    assert_eq!(selected, vec![&b"b x"[..], b"c", b"b y"]);
+
+    assert!(fasta.select_ids(&HashSet::new()).is_empty());
+}
+
+#[test]
+fn test_iter_case_folded() {
+    let fasta = parse_fasta_str(">a\nacGT\nn-*1\nXy[@").expect("Failed to parse FASTA");
+
+    assert_eq!(
+        fasta[0].iter_uppercase().collect::<Vec<_>>(),
+        b"ACGTN-*1XY[@"
+    );
+    assert_eq!(
+        fasta[0].iter_lowercase().collect::<Vec<_>>(),
+        b"acgtn-*1xy[@"
+    );
+}
+
+#[test]
+fn test_max_header_len() {
+    let options = ParseOptions::new().max_header_len(4);
+
+    let fasta = parse_fasta_with(b">abcd\nAC\n>\nGT", options).expect("Failed to parse FASTA");
+    assert_eq!(fasta.sequences.len(), 2);
+
+    assert!(matches!(
+        parse_fasta_with(b">a\nAC\n>abcde\nGT", options),
+        Err(ParseError::HeaderTooLong { offset: 6 })
+    ));
+    assert!(matches!(
+        parse_fasta_with(b">abcdefgh", options),
+        Err(ParseError::HeaderTooLong { offset: 0 })
+    ));
+    assert!(matches!(
+        parse_fasta_with(b">abc", options),
+        Err(ParseError::EmptySequence)
+    ));
+    assert!(parse_fasta_with(
+        b">abcdefgh\nAC",
+        ParseOptions::new().max_header_len(usize::MAX)
+    )
+    .is_ok());
+}
+
+#[test]
+fn test_find_motif() {
+    let fasta = parse_fasta_str(">a\nAAA\nAtt\n>b\nTTaa\n>c\n\n").expect("Failed to parse FASTA");
+
+    assert_eq!(fasta.find_motif(b"AA"), vec![(0, 0), (0, 1), (0, 2)]);
+    assert_eq!(
+        fasta.find_motif_ci(b"aA"),
+        vec![(0, 0), (0, 1), (0, 2), (1, 2)]
+    );
+    assert_eq!(fasta.find_motif_ci(b"TT"), vec![(0, 4), (1, 0)]);
+    assert_eq!(fasta.find_motif(b"TT"), vec![(1, 0)]);
+    assert!(fasta.find_motif(b"").is_empty());
+    assert!(fasta.find_motif_ci(b"").is_empty());
+    assert!(fasta.find_motif(b"AAAAAAAAAA").is_empty());
+}
+
+#[test]
+fn test_parse_single_fasta() {
+    let seq = parse_single_fasta(b">a desc\nAC\nGT\n\n").expect("Failed to parse FASTA");
+    assert_eq!(seq.description, b"a desc");
+    assert_eq!(seq.copy_sequential().as_ref(), b"ACGT");
+
+    assert!(matches!(
+        parse_single_fasta(b""),
+        Err(ParseError::NoRecords)
+    ));
+    assert!(matches!(
+        parse_single_fasta(b">a\nAC\n>"),
+        Err(ParseError::MultipleRecords { offset: 6 })
+    ));
+    assert!(matches!(
+        parse_single_fasta(b"AC"),
+        Err(ParseError::InvalidDescription { invalid: b'A' })
+    ));
+    assert!(matches!(
+        parse_single_fasta(b">a"),
+        Err(ParseError::EmptySequence)
+    ));
+}
+
+#[test]
+fn test_enumerate_records() {
+    let fasta = parse_fasta_str(">a\nAC\n>b\nGT\n>c\nTT").expect("Failed to parse FASTA");
+
+    let numbers = fasta
+        .enumerate_records()
+        .map(|(number, _)| number)
+        .collect::<Vec<_>>();
+    assert_eq!(numbers, vec![1, 2, 3]);
+    assert_eq!(fasta.enumerate_records().len(), 3);
+    assert_eq!(
+        fasta.enumerate_records().next().unwrap().1.description,
+        b"a"
+    );
+}
+
+#[test]
+fn test_parse_fasta_bounded() {
+    let data = b">a\nACGT";
+    assert_eq!(parse_fasta_bounded(data, 7).unwrap().sequences.len(), 1);
+    assert!(matches!(
+        parse_fasta_bounded(data, 6),
+        Err(ParseError::InputTooLarge { len: 7, limit: 6 })
+    ));
+    assert!(parse_fasta_bounded(b"", 0).unwrap().sequences.is_empty());
+}
+
+#[test]
+fn test_newline_only_sequences() {
+    let data = b">id\n\n\n>next\nACGT\n>single\n\n>crlf\r\n\r\n\r\n>direct\n>last\n\n";
+    for options in [
+        ParseOptions::new(),
+        ParseOptions::new().newline(Newline::Auto),
+    ] {
+        let fasta = parse_fasta_with(data, options).expect("Failed to parse FASTA");
+        assert_eq!(fasta.sequences.len(), 6);
+
+        let empty = fasta
+            .iter()
+            .map(FastaSequence::is_empty)
+            .collect::<Vec<_>>();
+        let crlf_empty = options.newline == Newline::Auto;
+        assert_eq!(empty, vec![true, false, true, crlf_empty, true, true]);
+        assert_eq!(
+            fasta.empty_sequence_indices(),
+            (0..6).filter(|&i| empty[i]).collect::<Vec<_>>()
+        );
+        assert_eq!(fasta.empty_sequence_count(), 4 + usize::from(crlf_empty));
+
+        for seq in fasta.iter().filter(|seq| seq.is_empty()) {
+            assert_eq!(seq.len(), 0);
+            assert!(seq.copy_sequential().is_empty());
+            assert_eq!(seq.iter().count(), 0);
+            assert_eq!(seq.line_count(), 0);
+        }
+    }
+}
+
+#[test]
+fn test_copy_sequential_edge_cases() {
+    for newline in [Newline::Lf, Newline::Auto] {
+        for (raw, expected) in [
+            (&b""[..], &b""[..]),
+            (b"\n", b""),
+            (b"\n\n", b""),
+            (b"ACGT\n", b"ACGT"),
+            (b"AC\n\nGT\n\n", b"ACGT"),
+            (b"\nAC", b"AC"),
+        ] {
+            let seq = FastaSequence {
+                description: b"",
+                sequence: raw,
+                newline,
+                strip_spaces: false,
+                span: 0..raw.len(),
+            };
+            assert_eq!(seq.copy_sequential().as_ref(), expected);
+            assert_eq!(seq.len(), expected.len());
+        }
+    }
+}
+
+#[test]
+fn test_percent_identity() {
+    let crlf = parse_fasta_with(
+        b">a\r\nAC\r\nGT\r\n",
+        ParseOptions::new().newline(Newline::Auto),
+    )
+    .expect("Failed to parse FASTA");
+    let fasta = parse_fasta(b">b\nA\nCGa\n>c\nTGCA\n>d\n\n>e\n\n").expect("Failed to parse FASTA");
+
+    assert_eq!(percent_identity(&crlf[0], &fasta[0]), Some(0.75));
+    assert_eq!(percent_identity(&fasta[0], &fasta[1]), Some(0.0));
+    assert_eq!(percent_identity(&fasta[1], &fasta[1]), Some(1.0));
+    assert_eq!(percent_identity(&fasta[0], &fasta[2]), None);
+    assert_eq!(percent_identity(&fasta[2], &fasta[3]), None);
+}
+
+#[test]
+fn test_residues_start_end_with() {
+    let crlf = parse_fasta_with(
+        b">a\r\nAC\r\nGT\r\n",
+        ParseOptions::new().newline(Newline::Auto),
+    )
+    .expect("Failed to parse FASTA");
+    let fasta = parse_fasta(b">b\nA\n\nCG\nT\n>c\n\n").expect("Failed to parse FASTA");
+
+    for seq in [&crlf[0], &fasta[0]] {
+        assert!(seq.residues_start_with(b""));
+        assert!(seq.residues_start_with(b"ACG"));
+        assert!(seq.residues_start_with(b"ACGT"));
+        assert!(!seq.residues_start_with(b"ACGTA"));
+        assert!(!seq.residues_start_with(b"AG"));
+
+        assert!(seq.residues_end_with(b""));
+        assert!(seq.residues_end_with(b"CGT"));
+        assert!(seq.residues_end_with(b"ACGT"));
+        assert!(!seq.residues_end_with(b"AACGT"));
+        assert!(!seq.residues_end_with(b"GG"));
+    }
+
+    assert!(fasta[1].residues_start_with(b""));
+    assert!(fasta[1].residues_end_with(b""));
+    assert!(!fasta[1].residues_start_with(b"A"));
+    assert!(!fasta[1].residues_end_with(b"A"));
+}
+
+#[test]
+fn test_split_at() {
+    let crlf = parse_fasta_with(
+        b">a\r\nAC\r\nGT\r\n",
+        ParseOptions::new().newline(Newline::Auto),
+    )
+    .expect("Failed to parse FASTA");
+    let fasta = parse_fasta(b">b\nA\n\nCG\nT\n>c\n\n").expect("Failed to parse FASTA");
+
+    for seq in [&crlf[0], &fasta[0]] {
+        for index in 0..=6 {
+            let (head, tail) = seq.split_at(index);
+            let split = index.min(4);
+            assert_eq!(head.as_ref(), &b"ACGT"[..split], "index {index}");
+            assert_eq!(tail.as_ref(), &b"ACGT"[split..], "index {index}");
+        }
+    }
+
+    let (head, tail) = fasta[1].split_at(1);
+    assert!(head.is_empty());
+    assert!(tail.is_empty());
+}
+
+#[test]
+fn test_records_with_spans() {
+    let data = b"\n\nACGT\n>a\nAC\nGT\n>\nA\n>c x\r\n\n>d";
+    let options = ParseOptions::new()
+        .skip_leading_blank_lines(true)
+        .assume_leading_sequence(true)
+        .newline(Newline::Auto);
+    let mut fasta =
+        parse_fasta_with(&data[..data.len() - 2], options).expect("Failed to parse FASTA");
+
+    let spans = fasta
+        .records_with_spans()
+        .map(|(seq, span)| (seq.description, span))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        spans,
+        vec![
+            (&b""[..], 2..7),
+            (&b"a"[..], 7..16),
+            (&b""[..], 16..20),
+            (&b"c x"[..], 20..27),
+        ]
+    );
+
+    // spans move with their records, and refer to the input each record was parsed from
+    let other = parse_fasta(b">e\nAC").expect("Failed to parse FASTA");
+    fasta.sequences.reverse();
+    fasta.sequences.push(other[0].clone());
+    let spans = fasta
+        .records_with_spans()
+        .map(|(_, span)| span)
+        .collect::<Vec<_>>();
+    assert_eq!(spans, vec![20..27, 16..20, 7..16, 2..7, 0..5]);
+
+    let (fasta, errors) = parse_fasta_recovering(data);
+    assert_eq!(errors.len(), 2);
+    let spans = fasta
+        .records_with_spans()
+        .map(|(_, span)| span)
+        .collect::<Vec<_>>();
+    assert_eq!(spans, vec![7..16, 16..20, 20..27]);
+}
+
+#[test]
+fn test_estimated_bytes() {
+    let data = b">a  x \r\nAC\r\n\n>\nGT\n>c\nA";
+    for options in [
+        ParseOptions::new(),
+        ParseOptions::new().newline(Newline::Auto),
+        ParseOptions::new().trim_descriptions(true),
+        ParseOptions::new().blank_line_separator(true),
+    ] {
+        let fasta = parse_fasta_with(data, options).expect("Failed to parse FASTA");
+        assert_eq!(fasta.estimated_bytes(), data.len());
+    }
+    assert_eq!(parse_fasta_lenient(data).0.estimated_bytes(), data.len());
+
+    // leading blank lines are not part of any record
+    let options = ParseOptions::new().skip_leading_blank_lines(true);
+    let fasta = parse_fasta_with(b"\n\n>a\nAC", options).expect("Failed to parse FASTA");
+    assert_eq!(fasta.estimated_bytes(), 5);
+
+    // records parsed from another input count with the size of their own span
+    let mut fasta = parse_fasta(b">a\nAC\n").expect("Failed to parse FASTA");
+    let other = parse_fasta(b">other\nACGT").expect("Failed to parse FASTA");
+    fasta.sequences.push(other[0].clone());
+    assert_eq!(fasta.estimated_bytes(), 6 + 11);
+}
+
+#[test]
+fn test_trim_descriptions() {
+    let data = b">  a b \t\r\nAC\r\n>\r\nGT\n> \n\n";
+    let options = ParseOptions::new().trim_descriptions(true);
+    let fasta = parse_fasta_with(data, options).expect("Failed to parse FASTA");
+
+    let descriptions = fasta.iter().map(|seq| seq.description).collect::<Vec<_>>();
+    assert_eq!(descriptions, vec![&b"a b"[..], b"", b""]);
+    assert_eq!(fasta[0].id(), b"a");
+    let spans = fasta
+        .records_with_spans()
+        .map(|(_, span)| span)
+        .collect::<Vec<_>>();
+    assert_eq!(spans, vec![0..14, 14..20, 20..24]);
+
+    // trimming is off by default
+    let fasta = parse_fasta_with(data, ParseOptions::new()).expect("Failed to parse FASTA");
+    assert_eq!(fasta[0].description, b"  a b \t\r");
+}
+
+#[test]
+fn test_blank_line_separator() {
+    let data = b"\n\nid1 a\nACGT\nAC\n\nid2\n\n\n>id3\nG>T\n";
+    let options = ParseOptions::new().blank_line_separator(true);
+    let fasta = parse_fasta_with(data, options).expect("Failed to parse FASTA");
+
+    let records = fasta
+        .iter()
+        .map(|seq| (seq.description, seq.copy_sequential()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        records,
+        vec![
+            (&b"id1 a"[..], b"ACGTAC".to_vec().into_boxed_slice()),
+            (&b"id2"[..], Box::default()),
+            (&b">id3"[..], b"G>T".to_vec().into_boxed_slice()),
+        ]
+    );
+    assert!(fasta[1].is_empty());
+    let spans = fasta
+        .records_with_spans()
+        .map(|(_, span)| span)
+        .collect::<Vec<_>>();
+    assert_eq!(spans, vec![2..17, 17..23, 23..32]);
+
+    let crlf = b"id1\r\nAC\r\n\r\nid2\r\nGT";
+    let fasta =
+        parse_fasta_with(crlf, options.newline(Newline::Auto)).expect("Failed to parse FASTA");
+    let descriptions = fasta.iter().map(|seq| seq.description).collect::<Vec<_>>();
+    assert_eq!(descriptions, vec![&b"id1"[..], b"id2"]);
+    assert_eq!(fasta[0].copy_sequential().as_ref(), b"AC");
+
+    // without the option, the input isn't FASTA
+    assert!(parse_fasta_with(crlf, ParseOptions::new()).is_err());
+    assert!(parse_fasta_with(b"\n\n", options)
+        .unwrap()
+        .sequences
+        .is_empty());
+    assert_eq!(
+        parse_fasta_with(b"id", options).unwrap()[0].description,
+        b"id"
+    );
+    assert!(matches!(
+        parse_fasta_with(b"\nid\nAC", options.max_header_len(1)),
+        Err(ParseError::HeaderTooLong { offset: 1 })
+    ));
+}
+
+#[test]
+fn test_logical_equality() {
+    let lf = parse_fasta(b">a x\nACG\n\nT\n>b\n\n>c\nA").expect("Failed to parse FASTA");
+    let crlf = parse_fasta_with(
+        b">a x\r\nAC\r\nGT\r\n>b\r\n>c\r\nA\r\n",
+        ParseOptions::new().newline(Newline::Auto),
+    )
+    .expect("Failed to parse FASTA");
+    assert_eq!(lf, crlf);
+    assert_eq!(lf[1], crlf[1]);
+
+    // CRs are residues in the default newline mode
+    let cr = parse_fasta(b">a x\r\nACGT\r\n>b\n>c\nA").expect("Failed to parse FASTA");
+    assert_ne!(lf, cr);
+
+    let other = parse_fasta(b">a x\nACGT\n>b\n\n>c\nG").expect("Failed to parse FASTA");
+    assert_ne!(lf, other);
+    assert_eq!(lf[0], other[0]);
+    assert_ne!(lf[0], other[1]);
+}
+
+#[test]
+fn test_index_by_id() {
+    let fasta = parse_fasta(b">b desc\nAC\n>a\nGT\n>b\nTT\n>\nA").expect("Failed to parse FASTA");
+    let index = fasta.index_by_id();
+
+    assert_eq!(index.len(), 3);
+    assert_eq!(index.get(&b"b"[..]), Some(&0));
+    assert_eq!(index.get(&b"a"[..]), Some(&1));
+    assert_eq!(index.get(&b""[..]), Some(&3));
+
+    assert_eq!(fasta.get_by_id(b"b").unwrap().description, b"b desc");
+    assert_eq!(fasta.get_by_id(b"").unwrap().description, b"");
+    assert!(fasta.get_by_id(b"c").is_none());
+}
+
+#[test]
+fn test_index_by_id_ci() {
+    let fasta = parse_fasta(b">Ab1 desc\nAC\n>aB1\nGT\n>c\nTT").expect("Failed to parse FASTA");
+    let index = fasta.index_by_id_ci();
+
+    assert_eq!(index.len(), 2);
+    assert_eq!(index.get(&b"AB1"[..]), Some(&0));
+    assert_eq!(index.get(&b"C"[..]), Some(&2));
+    assert_eq!(index.get(&b"c"[..]), None);
+
+    assert_eq!(fasta.get_by_id_ci(b"AB1").unwrap().description, b"Ab1 desc");
+    assert_eq!(fasta.get_by_id_ci(b"C").unwrap().description, b"c");
+    assert!(fasta.get_by_id_ci(b"ab").is_none());
+    assert!(fasta.get_by_id(b"AB1").is_none());
+}
+
+#[test]
+fn test_residue_chunks() {
+    let crlf = parse_fasta_with(
+        b">a\r\nACGTACG\r\n\r\nTT\r\n",
+        ParseOptions::new().newline(Newline::Auto),
+    )
+    .expect("Failed to parse FASTA");
+    let seq = &crlf[0];
+
+    for chunk in 1..=9 {
+        let chunks = seq.residue_chunks(chunk).collect::<Vec<_>>();
+        assert!(chunks.iter().all(|c| !c.is_empty() && c.len() <= chunk));
+        assert_eq!(chunks.concat(), b"ACGTACGTT");
+    }
+    assert_eq!(
+        seq.residue_chunks(3).collect::<Vec<_>>(),
+        vec![&b"ACG"[..], b"TAC", b"G", b"TT"]
+    );
+
+    let fasta = parse_fasta(b">b\n\n\n").expect("Failed to parse FASTA");
+    assert_eq!(fasta[0].residue_chunks(4).count(), 0);
+}
+
+#[test]
+#[should_panic(expected = "non-zero")]
+fn test_residue_chunks_zero() {
+    let fasta = parse_fasta(b">a\nAC").expect("Failed to parse FASTA");
+    let _ = fasta[0].residue_chunks(0);
+}
+
+#[test]
+fn test_common_id_prefix() {
+    let prefix = |data: &[u8]| {
+        parse_fasta(data)
+            .expect("Failed to parse FASTA")
+            .common_id_prefix()
+    };
+
+    assert_eq!(prefix(b""), b"");
+    assert_eq!(prefix(b">ENSG0001 x\nAC"), b"ENSG0001");
+    assert_eq!(
+        prefix(b">ENSG0001 x\nAC\n>ENSG0002\nA\n>ENSG01\nA"),
+        b"ENSG0"
+    );
+    assert_eq!(prefix(b">ENSG0001\nAC\n>ENSG0001\nA"), b"ENSG0001");
+    assert_eq!(prefix(b">ENSG0001\nAC\n>ENSG\nA"), b"ENSG");
+    assert_eq!(prefix(b">abc\nAC\n>xbc\nA\n>abc\nA"), b"");
+    assert_eq!(prefix(b">abc\nAC\n> abc\nA"), b"");
+}
+
+#[test]
+fn test_retain_residues() {
+    let crlf = parse_fasta_with(
+        b">a\r\nAC-G.\r\n\r\n--T1\r\n",
+        ParseOptions::new().newline(Newline::Auto),
+    )
+    .expect("Failed to parse FASTA");
+    let seq = &crlf[0];
+
+    assert_eq!(seq.ungapped().as_ref(), b"ACGT1");
+    assert_eq!(
+        seq.retain_residues(|c| c.is_ascii_uppercase()).as_ref(),
+        b"ACGT"
+    );
+    assert_eq!(seq.retain_residues(|_| true), seq.copy_sequential());
+    assert!(seq.retain_residues(|_| false).is_empty());
+}
+
+#[test]
+fn test_strip_sequence_spaces() {
+    let data = b">a x\nACG TAC\nGG\t G \n>b\r\n A C\r\n";
+    let options = ParseOptions::new().strip_sequence_spaces(true);
+    let fasta = parse_fasta_with(data, options).expect("Failed to parse FASTA");
+    let seq = &fasta[0];
+
+    assert_eq!(seq.description, b"a x");
+    assert_eq!(seq.copy_sequential().as_ref(), b"ACGTACGGG");
+    assert!(seq.iter().eq(b"ACGTACGGG"));
+    assert!(seq.iter_rev().eq(b"GGGCATGCA"));
+    assert_eq!(seq.len(), 9);
+    assert_eq!(seq.residues().as_ref(), b"ACGTACGGG");
+    assert!(seq.contains(b"GTACG"));
+    assert_eq!(seq.logical_to_physical(3), Some(4));
+    assert_eq!(seq.physical_to_logical(3), None);
+    assert_eq!(
+        seq.lines().collect::<Vec<_>>(),
+        vec![&b"ACG TAC"[..], b"GG\t G "]
+    );
+    assert_eq!(
+        seq.residue_chunks(2).collect::<Vec<_>>(),
+        vec![&b"AC"[..], b"G", b"TA", b"C", b"GG", b"G"]
+    );
+
+    // a CR is only skipped in Newline::Auto mode
+    assert_eq!(fasta[1].copy_sequential().as_ref(), b"AC\r");
+    let crlf =
+        parse_fasta_with(data, options.newline(Newline::Auto)).expect("Failed to parse FASTA");
+    assert_eq!(crlf[1].copy_sequential().as_ref(), b"AC");
+    assert!(matches!(crlf[1].residues(), Cow::Owned(_)));
+
+    let fasta = parse_fasta(data).expect("Failed to parse FASTA");
+    assert_eq!(fasta[0].copy_sequential().as_ref(), b"ACG TACGG\t G ");
+    assert_eq!(fasta[0].len(), 13);
+}
+
+#[test]
+fn test_stop_symbol() {
+    let fasta = parse_fasta_with(
+        b">a\r\nMK*\r\n\r\n>b\r\nMK*\r\nW\r\n>c\r\nMK**\r\n>d\r\nMK.\r\n>e\r\n\r\n",
+        ParseOptions::new().newline(Newline::Auto),
+    )
+    .expect("Failed to parse FASTA");
+
+    assert_eq!(fasta[0].trim_stop().as_ref(), b"MK");
+    assert!(!fasta[0].contains_internal_stop());
+    assert_eq!(fasta[1].trim_stop().as_ref(), b"MK*W");
+    assert!(fasta[1].contains_internal_stop());
+    assert_eq!(fasta[2].trim_stop().as_ref(), b"MK*");
+    assert!(fasta[2].contains_internal_stop());
+
+    assert_eq!(fasta[3].trim_stop().as_ref(), b"MK.");
+    assert_eq!(fasta[3].trim_stop_with(b'.').as_ref(), b"MK");
+    assert!(!fasta[3].contains_internal_stop_with(b'.'));
+    assert!(fasta[3].contains_internal_stop_with(b'M'));
+
+    assert!(fasta[4].trim_stop().is_empty());
+    assert!(!fasta[4].contains_internal_stop());
+}
+
+#[test]
+fn test_parse_fasta_utf8_checked() {
+    let fasta =
+        parse_fasta_utf8_checked(b">\xc3\xa4\n\xff\xfe\n>\nAC").expect("Failed to parse FASTA");
+    assert_eq!(fasta.sequences.len(), 2);
+    assert_eq!(fasta[0].copy_sequential().as_ref(), b"\xff\xfe");
+
+    // an invalid sequence isn't checked, but a description split from a multi-byte character is
+    let data = b">a\n\xff\n>b \xc3\nAC";
+    assert!(parse_fasta(data).is_ok());
+    assert!(matches!(
+        parse_fasta_utf8_checked(data),
+        Err(ParseError::InvalidUtf8Description { index: 1 })
+    ));
+    assert!(matches!(
+        parse_fasta_utf8_checked(b">a\n"),
+        Err(ParseError::EmptySequence)
+    ));
+}
+
+#[test]
+fn test_subsequence_and_flank() {
+    let crlf = parse_fasta_with(
+        b">a\r\nAC\r\nGTT\r\n\r\nGCA\r\n",
+        ParseOptions::new().newline(Newline::Auto),
+    )
+    .expect("Failed to parse FASTA");
+    let seq = &crlf[0];
+    let residues = seq.copy_sequential();
+    let len = residues.len();
+
+    for start in 0..=len + 1 {
+        for end in 0..=len + 1 {
+            let expected = residues
+                .get(start.min(len)..end.min(len))
+                .unwrap_or_default();
+            assert_eq!(
+                seq.subsequence(start, end).as_ref(),
+                expected,
+                "{start}..{end}"
+            );
+        }
+    }
+
+    assert_eq!(seq.flank(3, 1, 1).as_ref(), b"GTT");
+    assert_eq!(seq.flank(0, 5, 0).as_ref(), b"A");
+    assert_eq!(seq.flank(7, 1, 5).as_ref(), b"CA");
+    assert_eq!(seq.flank(10, 3, 3).as_ref(), b"A");
+    assert_eq!(seq.flank(2, usize::MAX, usize::MAX), residues);
+    assert!(seq.flank(20, 3, 3).is_empty());
+}
+
+#[test]
+fn test_dedup_by_id_longest() {
+    let fasta =
+        parse_fasta(b">x 1\nAC\nG\n>y\nA\n>x 2\nACGT\n>z\n\n>x 3\nAC\nGT\n>y 2\nC\n>z 2\nC")
+            .expect("Failed to parse FASTA");
+
+    let descriptions = fasta
+        .dedup_by_id_longest()
+        .iter()
+        .map(|seq| seq.description)
+        .collect::<Vec<_>>();
+    assert_eq!(descriptions, vec![&b"x 2"[..], b"y", b"z 2"]);
+
+    let empty = parse_fasta(b"").expect("Failed to parse FASTA");
+    assert!(empty.dedup_by_id_longest().is_empty());
+}
+
+#[test]
+fn test_parse_error_display() {
+    let message = |data: &[u8]| parse_fasta(data).unwrap_err().to_string();
+
+    assert_eq!(
+        message(b"ACGT"),
+        "invalid description start character 'A' (byte 0x41); FASTA records must begin with '>'"
+    );
+    assert_eq!(
+        message(b"\nACGT"),
+        "invalid description start character '\\n' (byte 0x0A); FASTA records must begin with '>'"
+    );
+    assert_eq!(
+        message(b">a\nAC\n>b\n"),
+        "the description at the end of the input has no following sequence"
+    );
+
+    let mut allowed = [false; 256];
+    allowed[b'A' as usize] = true;
+    assert_eq!(
+        parse_fasta_validated(b">a\nAA\n\xff", &allowed)
+            .unwrap_err()
+            .to_string(),
+        "invalid sequence character '\\xff' (byte 0xFF) at offset 6"
+    );
+    assert_eq!(
+        parse_single_fasta(b">a\nAC\n>b\nGT")
+            .unwrap_err()
+            .to_string(),
+        "expected a single record, but another record starts at offset 6"
+    );
+    assert_eq!(
+        parse_fasta_bounded(b">a\nAC", 2).unwrap_err().to_string(),
+        "the input of 5 bytes is larger than the maximum of 2 bytes"
+    );
+    assert_eq!(
+        parse_fasta_with(b">abc\nAC", ParseOptions::new().max_header_len(2))
+            .unwrap_err()
+            .to_string(),
+        "the description at offset 0 is longer than the maximum length"
+    );
+}
@@ -0,0 +1,524 @@
+use crate::{Fasta, FastaReader, FastaSequence, OwnedFastaSequence};
+use memchr::memchr;
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, Read, Write};
+use std::path::PathBuf;
+
+/// Options to configure how [`write_fasta`] formats the output.
+///
+/// [`write_fasta`]: crate::write_fasta
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct WriteOptions {
+    /// The number of sequence characters per line.
+    /// Sequences are re-wrapped to this width, regardless of their wrapping in the input.
+    /// A width of `0` writes each sequence on a single line.
+    ///
+    /// Defaults to `60`.
+    pub line_width: usize,
+
+    /// Whether a newline is written before the first record.
+    /// This is needed when appending to a file that doesn't end in a newline,
+    /// because otherwise the first description would be merged into the last line of the file.
+    /// No newline is written if there are no records.
+    ///
+    /// Defaults to `false`.
+    pub leading_newline: bool,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        Self {
+            line_width: 60,
+            leading_newline: false,
+        }
+    }
+}
+
+impl WriteOptions {
+    /// Create the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the [`line_width`] sequences are wrapped at.
+    ///
+    /// [`line_width`]: WriteOptions::line_width
+    #[must_use]
+    pub fn line_width(mut self, line_width: usize) -> Self {
+        self.line_width = line_width;
+        self
+    }
+
+    /// Set whether a newline is written before the first record (see [`leading_newline`]).
+    ///
+    /// [`leading_newline`]: WriteOptions::leading_newline
+    #[must_use]
+    pub fn leading_newline(mut self, leading_newline: bool) -> Self {
+        self.leading_newline = leading_newline;
+        self
+    }
+}
+
+/// Write all sequences of a FASTA file to `out`.
+/// Each record is written as a description line starting with `>`,
+/// followed by the sequence wrapped according to the [`WriteOptions`].
+/// Newlines are always written unix-style (`LF`), and every line ends in a newline.
+/// An empty sequence is written as an empty line, so the output can be parsed by [`parse_fasta`]
+/// even if the last record is empty.
+///
+/// The output is written with many small writes, so `out` should be buffered.
+///
+/// ```rust
+/// # use fire_fasta::{parse_fasta, write_fasta, WriteOptions};
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let fasta = parse_fasta(b">Sample1\nACG\nTCA\n>Sample2\nACGTCC")?;
+///
+/// let mut out = Vec::new();
+/// write_fasta(&fasta, &mut out, &WriteOptions::new().line_width(4))?;
+/// assert_eq!(out, b">Sample1\nACGT\nCA\n>Sample2\nACGT\nCC\n");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns any I/O error of `out`.
+///
+/// [`parse_fasta`]: crate::parse_fasta
+pub fn write_fasta<W: Write>(fasta: &Fasta, out: &mut W, opts: &WriteOptions) -> io::Result<()> {
+    write_fasta_records(fasta, out, opts)
+}
+
+/// Write a selection of sequences to `out`, formatted like [`write_fasta`].
+/// This allows writing a filtered subset of the sequences of a file,
+/// without building a [`Fasta`] from it first.
+///
+/// ```rust
+/// # use fire_fasta::{parse_fasta, write_fasta_records, WriteOptions};
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let fasta = parse_fasta(b">Sample1\nACG\n>Sample2\nACGTCC")?;
+/// let long = fasta.iter().filter(|seq| seq.len() > 3);
+///
+/// let mut out = Vec::new();
+/// write_fasta_records(long, &mut out, &WriteOptions::new())?;
+/// assert_eq!(out, b">Sample2\nACGTCC\n");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns any I/O error of `out`.
+pub fn write_fasta_records<'r, 'a: 'r, W: Write>(
+    records: impl IntoIterator<Item = &'r FastaSequence<'a>>,
+    out: &mut W,
+    opts: &WriteOptions,
+) -> io::Result<()> {
+    for (i, seq) in records.into_iter().enumerate() {
+        write_record(out, i == 0, seq.description, seq.segments(), opts)?;
+    }
+    Ok(())
+}
+
+/// Write all sequences of a FASTA file to `out` ordered by a key, formatted like [`write_fasta`].
+/// The input is not modified, instead the indices of the sequences are sorted,
+/// so the key function is called once per sequence.
+/// The sort is stable, so sequences with equal keys are written in their input order.
+///
+/// ```rust
+/// # use fire_fasta::{parse_fasta, write_fasta_sorted_by, WriteOptions};
+/// # use std::cmp::Reverse;
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let fasta = parse_fasta(b">Sample1\nACG\n>Sample2\nACGTCC")?;
+///
+/// let mut out = Vec::new();
+/// write_fasta_sorted_by(&fasta, &mut out, |seq| Reverse(seq.len()), &WriteOptions::new())?;
+/// assert_eq!(out, b">Sample2\nACGTCC\n>Sample1\nACG\n");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns any I/O error of `out`.
+pub fn write_fasta_sorted_by<'a, W, K, F>(
+    fasta: &Fasta<'a>,
+    out: &mut W,
+    mut key_fn: F,
+    opts: &WriteOptions,
+) -> io::Result<()>
+where
+    W: Write,
+    K: Ord,
+    F: FnMut(&FastaSequence<'a>) -> K,
+{
+    let mut order = (0..fasta.sequences.len()).collect::<Vec<_>>();
+    order.sort_by_cached_key(|&i| key_fn(&fasta.sequences[i]));
+    write_fasta_records(order.into_iter().map(|i| &fasta.sequences[i]), out, opts)
+}
+
+/// Write all sequences of a FASTA file to `out` with descriptions computed by `header_fn`,
+/// formatted like [`write_fasta`].
+/// This allows renaming records on output, for example to prefix them with a sample name or to
+/// renumber them, without modifying the parsed file.
+/// The callback returns the new description without the leading `>`,
+/// and the sequences are written unchanged.
+///
+/// ```rust
+/// # use fire_fasta::{parse_fasta, write_fasta_with_header_fn, WriteOptions};
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let fasta = parse_fasta(b">read1 lane=2\nACG\n>read2 lane=3\nACGTCC")?;
+///
+/// let mut out = Vec::new();
+/// write_fasta_with_header_fn(&fasta, &mut out, |seq| {
+///     [&b"sampleA_"[..], seq.id()].concat()
+/// }, &WriteOptions::new())?;
+/// assert_eq!(out, b">sampleA_read1\nACG\n>sampleA_read2\nACGTCC\n");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns an error of kind [`InvalidInput`] if a description returned by `header_fn` contains a
+/// newline, which would corrupt the output.
+/// Records before the failing record have already been written to `out`.
+/// Otherwise, returns any I/O error of `out`.
+///
+/// [`InvalidInput`]: io::ErrorKind::InvalidInput
+pub fn write_fasta_with_header_fn<W, F>(
+    fasta: &Fasta,
+    out: &mut W,
+    mut header_fn: F,
+    opts: &WriteOptions,
+) -> io::Result<()>
+where
+    W: Write,
+    F: FnMut(&FastaSequence) -> Vec<u8>,
+{
+    for (i, seq) in fasta.sequences.iter().enumerate() {
+        write_record(out, i == 0, &header_fn(seq), seq.segments(), opts)?;
+    }
+    Ok(())
+}
+
+/// Stream the records of a FASTA file from `reader`, apply `f` to each record,
+/// and write the modified records to `out`, formatted like [`write_fasta`].
+/// Only one record is held in memory at a time, so arbitrarily large files can be transformed.
+///
+/// The input is parsed with a [`FastaReader`], and buffered internally,
+/// but `out` should be buffered.
+///
+/// ```rust
+/// # use fire_fasta::{transform_fasta, WriteOptions};
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let input = &b">Sample1 desc\nacg\ntca\n>Sample2\nACGTCC"[..];
+///
+/// let mut out = Vec::new();
+/// transform_fasta(input, &mut out, |seq| {
+///     seq.sequence_mut().make_ascii_uppercase();
+/// }, &WriteOptions::new())?;
+/// assert_eq!(out, b">Sample1 desc\nACGTCA\n>Sample2\nACGTCC\n");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns any error of [`FastaReader::next_record`].
+/// Returns an error of kind [`InvalidInput`] if `f` inserts a newline into a description,
+/// which would corrupt the output.
+/// Otherwise, returns any I/O error of `out`.
+/// Records before the failing record have already been written to `out`.
+///
+/// [`InvalidInput`]: io::ErrorKind::InvalidInput
+pub fn transform_fasta<R: Read, W: Write, F: FnMut(&mut OwnedFastaSequence)>(
+    reader: R,
+    out: &mut W,
+    mut f: F,
+    opts: &WriteOptions,
+) -> io::Result<()> {
+    let mut reader = FastaReader::new(reader);
+    let mut writer = FastaWriter::new(out, *opts);
+    while let Some(record) = reader.next_record()? {
+        let mut record = OwnedFastaSequence::from(&record);
+        f(&mut record);
+        writer.write_record(record.description(), record.sequence())?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+/// Stream the records of a FASTA file from `reader`, and write them to `writer` re-wrapped to a
+/// uniform `line_width`, like [`write_fasta`] with [`WriteOptions::line_width`].
+/// Only one record is held in memory at a time, so arbitrarily large files can be normalized.
+/// A width of `0` writes each sequence on a single line.
+///
+/// The input is parsed with a [`FastaReader`], and buffered internally,
+/// but `writer` should be buffered.
+///
+/// ```rust
+/// # use fire_fasta::rewrap_fasta;
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let input = &b">Sample1\nACG\nTCA\nG\n>Sample2\nACGTCC"[..];
+///
+/// let mut out = Vec::new();
+/// rewrap_fasta(input, &mut out, 4)?;
+/// assert_eq!(out, b">Sample1\nACGT\nCAG\n>Sample2\nACGT\nCC\n");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns any error of [`FastaReader::next_record`], and any I/O error of `writer`.
+/// Records before the failing record have already been written to `writer`.
+pub fn rewrap_fasta<R: Read, W: Write>(reader: R, writer: W, line_width: usize) -> io::Result<()> {
+    let mut reader = FastaReader::new(reader);
+    let mut writer = FastaWriter::new(writer, WriteOptions::new().line_width(line_width));
+    while let Some(record) = reader.next_record()? {
+        writer.write_record(record.description, record.sequence)?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+/// A writer that emits FASTA records one at a time, formatted like [`write_fasta`].
+/// This is the streaming counterpart to the parser,
+/// for tools that generate records incrementally instead of holding them in a [`Fasta`].
+///
+/// ```rust
+/// # use fire_fasta::{FastaWriter, WriteOptions};
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let mut writer = FastaWriter::new(Vec::new(), WriteOptions::new().line_width(4));
+/// writer.write_record(b"orf1", b"MSTILAA")?;
+/// writer.write_record(b"orf2", b"MK")?;
+///
+/// let out = writer.finish()?;
+/// assert_eq!(out, b">orf1\nMSTI\nLAA\n>orf2\nMK\n");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FastaWriter<W: Write> {
+    out: W,
+    opts: WriteOptions,
+    records: usize,
+}
+
+impl<W: Write> FastaWriter<W> {
+    /// Create a writer that writes records to `out`, formatted according to the options.
+    /// The records are written with many small writes, so `out` should be buffered.
+    pub fn new(out: W, opts: WriteOptions) -> Self {
+        Self {
+            out,
+            opts,
+            records: 0,
+        }
+    }
+
+    /// Write a record with the given description and sequence.
+    /// The sequence is wrapped according to the [`WriteOptions`],
+    /// and any newlines in it are skipped.
+    ///
+    /// # Errors
+    /// Returns an error of kind [`InvalidInput`] if the description contains a newline,
+    /// which would corrupt the output.
+    /// The record is not written and not counted in that case.
+    /// Otherwise, returns any I/O error of the underlying writer.
+    ///
+    /// [`InvalidInput`]: io::ErrorKind::InvalidInput
+    pub fn write_record(&mut self, description: &[u8], sequence: &[u8]) -> io::Result<()> {
+        write_record(
+            &mut self.out,
+            self.records == 0,
+            description,
+            sequence.split(|&c| c == b'\n'),
+            &self.opts,
+        )?;
+        self.records += 1;
+        Ok(())
+    }
+
+    /// Returns the number of records written so far.
+    pub fn records(&self) -> usize {
+        self.records
+    }
+
+    /// Flush the underlying writer and return it.
+    ///
+    /// # Errors
+    /// Returns any I/O error of flushing the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+/// The highest Phred quality score that can be encoded as a printable Phred+33 character.
+const MAX_PHRED_QUALITY: u8 = b'~' - 33;
+
+/// Write all sequences of a FASTA file to `out` in FASTQ format, with the same quality for every
+/// residue.
+/// Each record is written as a `@` line with the description, the sequence on a single line,
+/// a `+` line, and a quality line of the same length as the sequence.
+/// The quality is a Phred score, which is encoded as a Phred+33 character.
+///
+/// The output is written with many small writes, so `out` should be buffered.
+///
+/// ```rust
+/// # use fire_fasta::{parse_fasta, write_fastq};
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let fasta = parse_fasta(b">read1\nACG\nT")?;
+///
+/// let mut out = Vec::new();
+/// write_fastq(&fasta, &mut out, 40)?;
+/// assert_eq!(out, b"@read1\nACGT\n+\nIIII\n");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns an error of kind [`InvalidInput`] if `default_qual` is greater than `93`,
+/// which is the highest score that can be encoded.
+/// Otherwise, returns any I/O error of `out`.
+///
+/// [`InvalidInput`]: io::ErrorKind::InvalidInput
+pub fn write_fastq<W: Write>(fasta: &Fasta, out: &mut W, default_qual: u8) -> io::Result<()> {
+    if default_qual > MAX_PHRED_QUALITY {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Phred quality {default_qual} exceeds the maximum of {MAX_PHRED_QUALITY}"),
+        ));
+    }
+
+    let quality = [default_qual + 33; 64];
+    for seq in &fasta.sequences {
+        out.write_all(b"@")?;
+        out.write_all(seq.description)?;
+        out.write_all(b"\n")?;
+
+        let mut len = 0;
+        for segment in seq.segments() {
+            out.write_all(segment)?;
+            len += segment.len();
+        }
+        out.write_all(b"\n+\n")?;
+
+        while len > 0 {
+            let take = len.min(quality.len());
+            out.write_all(&quality[..take])?;
+            len -= take;
+        }
+        out.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+impl Fasta<'_> {
+    /// Write each sequence to its own file, formatted like [`write_fasta`].
+    /// The path of each file is determined by `path_fn`, for example from the ID of the sequence.
+    /// Existing files are truncated.
+    ///
+    /// The callback must return a unique path for every sequence:
+    /// If two sequences map to the same path, the file only contains the later sequence.
+    ///
+    /// ```rust,no_run
+    /// # use fire_fasta::{parse_fasta, WriteOptions};
+    /// # use std::error::Error;
+    /// # use std::path::PathBuf;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let fasta = parse_fasta(b">Sample1\nACGT\n>Sample2\nACGTCC")?;
+    ///
+    /// fasta.write_split(
+    ///     |seq| PathBuf::from(format!("{}.fa", String::from_utf8_lossy(seq.id()))),
+    ///     &WriteOptions::new(),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns any I/O error of creating or writing a file.
+    /// Files of sequences before the failing sequence have already been written.
+    pub fn write_split<F: FnMut(&FastaSequence) -> PathBuf>(
+        &self,
+        mut path_fn: F,
+        opts: &WriteOptions,
+    ) -> io::Result<()> {
+        for seq in &self.sequences {
+            let mut out = BufWriter::new(File::create(path_fn(seq))?);
+            write_fasta_records(std::iter::once(seq), &mut out, opts)?;
+            out.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Write a single record, whose sequence is given as newline-free segments.
+/// If the record is the `first` record of the output, the [`leading_newline`] is written before
+/// it.
+///
+/// Returns an error of kind [`InvalidInput`] without writing anything,
+/// if the description contains a newline, which would corrupt the output.
+///
+/// [`leading_newline`]: WriteOptions::leading_newline
+/// [`InvalidInput`]: io::ErrorKind::InvalidInput
+pub(crate) fn write_record<'s, W: Write>(
+    out: &mut W,
+    first: bool,
+    description: &[u8],
+    segments: impl IntoIterator<Item = &'s [u8]>,
+    opts: &WriteOptions,
+) -> io::Result<()> {
+    if memchr(b'\n', description).is_some() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "description contains a newline",
+        ));
+    }
+
+    if first && opts.leading_newline {
+        out.write_all(b"\n")?;
+    }
+    out.write_all(b">")?;
+    out.write_all(description)?;
+    out.write_all(b"\n")?;
+
+    let mut column = 0;
+    let mut empty = true;
+    for mut segment in segments {
+        empty &= segment.is_empty();
+        if opts.line_width == 0 {
+            out.write_all(segment)?;
+            column += segment.len();
+            continue;
+        }
+
+        while !segment.is_empty() {
+            let take = (opts.line_width - column).min(segment.len());
+            out.write_all(&segment[..take])?;
+            segment = &segment[take..];
+            column += take;
+
+            if column == opts.line_width {
+                out.write_all(b"\n")?;
+                column = 0;
+            }
+        }
+    }
+
+    // an empty sequence is written as an empty line, because a description without any line
+    // after it is not a valid last record
+    if column > 0 || empty {
+        out.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
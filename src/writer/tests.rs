@@ -0,0 +1,260 @@
+use crate::{
+    parse_fasta, parse_fasta_with, rewrap_fasta, transform_fasta, write_fasta, write_fasta_records,
+    write_fasta_sorted_by, write_fasta_with_header_fn, write_fastq, FastaWriter, Newline,
+    ParseOptions, WriteOptions,
+};
+
+#[test]
+fn test_write_rewrapped() {
+    let fasta =
+        parse_fasta(b">a desc\nACGTA\nCG\n\nTA\n>b\nACG\n>c\n\n").expect("Failed to parse FASTA");
+
+    let mut out = Vec::new();
+    write_fasta(&fasta, &mut out, &WriteOptions::new().line_width(3)).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        ">a desc\nACG\nTAC\nGTA\n>b\nACG\n>c\n\n"
+    );
+
+    let mut out = Vec::new();
+    write_fasta(&fasta, &mut out, &WriteOptions::new().line_width(0)).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        ">a desc\nACGTACGTA\n>b\nACG\n>c\n\n"
+    );
+}
+
+#[test]
+fn test_write_empty_last_record() {
+    let input = b">a\nAC\n>b\n\n";
+    let fasta = parse_fasta(input).expect("Failed to parse FASTA");
+
+    let mut out = Vec::new();
+    write_fasta(&fasta, &mut out, &WriteOptions::new()).unwrap();
+    assert_eq!(out, input);
+    assert_eq!(parse_fasta(&out).expect("Failed to parse FASTA"), fasta);
+}
+
+#[test]
+fn test_write_crlf_input() {
+    let seq = b">a\r\nACG\r\nTA\r\n";
+    let fasta = parse_fasta_with(seq, ParseOptions::new().newline(Newline::Auto)).unwrap();
+
+    let mut out = Vec::new();
+    write_fasta(&fasta, &mut out, &WriteOptions::default()).unwrap();
+    assert_eq!(out, b">a\nACGTA\n");
+}
+
+#[test]
+fn test_write_fastq() {
+    let residues = "ACGT".repeat(40);
+    let data = format!(">a desc\n{residues}\n>b\n\n>c\nA\nC\n");
+    let fasta = parse_fasta(data.as_bytes()).expect("Failed to parse FASTA");
+
+    let mut out = Vec::new();
+    write_fastq(&fasta, &mut out, 0).unwrap();
+    let expected = format!(
+        "@a desc\n{residues}\n+\n{}\n@b\n\n+\n\n@c\nAC\n+\n!!\n",
+        "!".repeat(160)
+    );
+    assert_eq!(String::from_utf8(out).unwrap(), expected);
+
+    let mut out = Vec::new();
+    write_fastq(&fasta, &mut out, 93).unwrap();
+    assert!(out.ends_with(b"\nAC\n+\n~~\n"));
+
+    let error = write_fastq(&fasta, &mut Vec::new(), 94).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_write_records() {
+    let fasta = parse_fasta(b">a\nAC\n>b\nGTT\n>c\nT").expect("Failed to parse FASTA");
+    let selected = vec![&fasta.sequences[2], &fasta.sequences[0]];
+
+    let mut out = Vec::new();
+    write_fasta_records(selected, &mut out, &WriteOptions::new()).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), ">c\nT\n>a\nAC\n");
+
+    let mut out = Vec::new();
+    write_fasta_records(&[], &mut out, &WriteOptions::new()).unwrap();
+    assert!(out.is_empty());
+}
+
+#[test]
+fn test_write_leading_newline() {
+    let fasta = parse_fasta(b">a\nAC\n>b\nGT").expect("Failed to parse FASTA");
+    let opts = WriteOptions::new().leading_newline(true);
+
+    let mut out = b">existing\nTT".to_vec();
+    write_fasta(&fasta, &mut out, &opts).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        ">existing\nTT\n>a\nAC\n>b\nGT\n"
+    );
+
+    let mut out = Vec::new();
+    write_fasta_records(&[], &mut out, &opts).unwrap();
+    assert!(out.is_empty());
+}
+
+#[test]
+fn test_transform_fasta() {
+    let input = b">a x\nacg\nt\n>b\nTT\n>c\nGG";
+
+    let mut out = Vec::new();
+    let mut index = 0;
+    transform_fasta(
+        &input[..],
+        &mut out,
+        |seq| {
+            *seq.description_mut() = format!("record{index}").into_bytes();
+            seq.sequence_mut().make_ascii_uppercase();
+            index += 1;
+        },
+        &WriteOptions::new().line_width(2),
+    )
+    .unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        ">record0\nAC\nGT\n>record1\nTT\n>record2\nGG\n"
+    );
+
+    let mut out = Vec::new();
+    let error = transform_fasta(&b">a\nAC\n>b\n"[..], &mut out, |_| {}, &WriteOptions::new());
+    assert_eq!(error.unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(out, b">a\nAC\n");
+    let mut out = Vec::new();
+    let error = transform_fasta(
+        &b">a\nAC\n>b\nGT"[..],
+        &mut out,
+        |seq| seq.description_mut().extend_from_slice(b"\n>c"),
+        &WriteOptions::new(),
+    );
+    assert_eq!(error.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+    assert!(out.is_empty());
+}
+
+#[test]
+fn test_write_sorted_by() {
+    let fasta = parse_fasta(b">c\nAC\n>a\nGTT\n>b\nT\n>a\nGG").expect("Failed to parse FASTA");
+
+    let mut out = Vec::new();
+    write_fasta_sorted_by(&fasta, &mut out, |seq| seq.id(), &WriteOptions::new()).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        ">a\nGTT\n>a\nGG\n>b\nT\n>c\nAC\n"
+    );
+    assert_eq!(fasta.sequences[0].description, b"c");
+}
+
+#[test]
+fn test_fasta_writer() {
+    let mut writer = FastaWriter::new(Vec::new(), WriteOptions::new().line_width(3));
+    writer.write_record(b"a desc", b"ACGTA\nCG").unwrap();
+    writer.write_record(b"b", b"").unwrap();
+    writer.write_record(b"c", b"ACG").unwrap();
+    assert_eq!(writer.records(), 3);
+    assert_eq!(
+        String::from_utf8(writer.finish().unwrap()).unwrap(),
+        ">a desc\nACG\nTAC\nG\n>b\n\n>c\nACG\n"
+    );
+
+    let mut out = b">existing\nTT".to_vec();
+    let mut writer = FastaWriter::new(&mut out, WriteOptions::new().leading_newline(true));
+    writer.write_record(b"a", b"AC").unwrap();
+    writer.write_record(b"b", b"GT").unwrap();
+    writer.finish().unwrap();
+    assert_eq!(out, b">existing\nTT\n>a\nAC\n>b\nGT\n");
+    let mut writer = FastaWriter::new(Vec::new(), WriteOptions::new().leading_newline(true));
+    let error = writer.write_record(b"a\n>b", b"AC");
+    assert_eq!(error.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(writer.records(), 0);
+    writer.write_record(b"a", b"AC").unwrap();
+    assert_eq!(writer.finish().unwrap(), b"\n>a\nAC\n");
+}
+
+#[test]
+fn test_rewrap_fasta() {
+    let mut input = Vec::new();
+    for (i, len) in [(0, 150), (1, 70), (2, 0), (3, 59)] {
+        input.extend_from_slice(format!(">seq{i} desc\n").as_bytes());
+        let residues = (0..len).map(|j| b"ACGT"[(i + j) % 4]).collect::<Vec<_>>();
+        for line in residues.chunks(70) {
+            input.extend_from_slice(line);
+            input.push(b'\n');
+        }
+        if len == 0 {
+            input.push(b'\n');
+        }
+    }
+
+    let mut out = Vec::new();
+    rewrap_fasta(&input[..], &mut out, 60).unwrap();
+    assert_eq!(
+        parse_fasta(&out).expect("Failed to parse FASTA"),
+        parse_fasta(&input).expect("Failed to parse FASTA")
+    );
+    let lines = out
+        .split(|&c| c == b'\n')
+        .filter(|line| !line.starts_with(b">"))
+        .map(<[u8]>::len)
+        .collect::<Vec<_>>();
+    assert_eq!(lines, vec![60, 60, 30, 60, 10, 0, 59, 0]);
+}
+
+#[test]
+fn test_write_with_header_fn() {
+    let fasta = parse_fasta(b">a x\nACG\nT\n>b\n\n>c y\nGG").expect("Failed to parse FASTA");
+    let opts = WriteOptions::new().line_width(3).leading_newline(true);
+
+    let mut out = Vec::new();
+    let mut index = 0;
+    write_fasta_with_header_fn(
+        &fasta,
+        &mut out,
+        |seq| {
+            index += 1;
+            format!("{index} {}", String::from_utf8_lossy(seq.description)).into_bytes()
+        },
+        &opts,
+    )
+    .unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "\n>1 a x\nACG\nT\n>2 b\n\n>3 c y\nGG\n"
+    );
+    assert_eq!(fasta[0].description, b"a x");
+
+    let mut out = Vec::new();
+    let error = write_fasta_with_header_fn(
+        &fasta,
+        &mut out,
+        |seq| [seq.description, b"\n"].concat(),
+        &WriteOptions::new(),
+    );
+    assert_eq!(error.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+    assert!(out.is_empty());
+}
+
+#[test]
+fn test_write_split() {
+    let dir = std::env::temp_dir().join(format!("fire-fasta-split-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let fasta = parse_fasta(b">a x\nACGT\nA\n>b\n>c\nGG").unwrap();
+    fasta
+        .write_split(
+            |seq| dir.join(format!("{}.fa", String::from_utf8_lossy(seq.id()))),
+            &WriteOptions::new().line_width(3),
+        )
+        .unwrap();
+
+    let read = |name: &str| std::fs::read(dir.join(name)).unwrap();
+    assert_eq!(read("a.fa"), b">a x\nACG\nTA\n");
+    assert_eq!(read("b.fa"), b">b\n\n");
+    assert_eq!(read("c.fa"), b">c\nGG\n");
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 3);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}